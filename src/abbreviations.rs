@@ -0,0 +1,254 @@
+use crate::morse::Morse;
+
+/// Abréviation usuelle en télégraphie.
+struct Abbreviation {
+    /// Abréviation, telle qu'elle est transmise.
+    abbreviation: &'static str,
+    /// Sens en clair : le premier sert au développement, tous servent à l'abréviation.
+    meanings: &'static [&'static str],
+    /// `false` pour les abréviations de procédure (`DE`, `K`…), trop ambiguës pour remplacer
+    /// un mot d'un texte en clair.
+    compress: bool,
+}
+
+const fn entry(
+    abbreviation: &'static str,
+    meanings: &'static [&'static str],
+    compress: bool,
+) -> Abbreviation {
+    Abbreviation {
+        abbreviation,
+        meanings,
+        compress,
+    }
+}
+
+/// Codes Q et abréviations courantes, avec leur sens en anglais.
+const ABBREVIATIONS: &[Abbreviation] = &[
+    entry("QTH", &["my location is", "my location"], true),
+    entry("QTH?", &["what is your location?"], true),
+    entry("QRZ?", &["who is calling me?"], true),
+    entry("QRL", &["this frequency is in use"], true),
+    entry("QRL?", &["is this frequency in use?"], true),
+    entry("QRM", &["interference"], true),
+    entry("QRN", &["static"], true),
+    entry("QRS", &["send more slowly"], true),
+    entry("QRQ", &["send faster"], true),
+    entry("QRP", &["low power"], true),
+    entry("QRO", &["high power"], true),
+    entry("QRT", &["stopping transmission"], true),
+    entry("QRU", &["I have nothing for you"], true),
+    entry("QRV", &["I am ready"], true),
+    entry("QRX", &["wait"], true),
+    entry("QSB", &["fading"], true),
+    entry("QSL", &["I confirm receipt"], true),
+    entry("QSL?", &["can you confirm receipt?"], true),
+    entry("QSO", &["contact"], true),
+    entry("QSY", &["change frequency"], true),
+    entry("73", &["best regards"], true),
+    entry("88", &["love and kisses"], true),
+    entry("ABT", &["about"], true),
+    entry("AGN", &["again"], true),
+    entry("ANT", &["antenna"], true),
+    entry("BK", &["break"], false),
+    entry("BTU", &["back to you"], true),
+    entry("CQ", &["calling any station"], false),
+    entry("CUAGN", &["see you again"], true),
+    entry("CUL", &["see you later"], true),
+    entry("DE", &["from"], false),
+    entry("DR", &["dear"], true),
+    entry("ES", &["and"], true),
+    entry("FB", &["fine business", "excellent"], true),
+    entry("FER", &["for"], true),
+    entry("GA", &["good afternoon"], true),
+    entry("GE", &["good evening"], true),
+    entry("GL", &["good luck"], true),
+    entry("GM", &["good morning"], true),
+    entry("GUD", &["good"], true),
+    entry("HPE", &["hope"], true),
+    entry("HR", &["here"], true),
+    entry("HW?", &["how do you copy?"], true),
+    entry("K", &["over"], false),
+    entry("NR", &["number"], true),
+    entry("NW", &["now"], true),
+    entry("OM", &["old man"], true),
+    entry("OP", &["operator"], true),
+    entry("PSE", &["please"], true),
+    entry("PWR", &["power"], true),
+    entry("R", &["received"], false),
+    entry("RPT", &["report"], true),
+    entry("RST", &["signal report"], true),
+    entry("SRI", &["sorry"], true),
+    entry("TEMP", &["temperature"], true),
+    entry("TNX", &["thanks"], true),
+    entry("TU", &["thank you"], true),
+    entry("UR", &["your", "you are"], true),
+    entry("VY", &["very"], true),
+    entry("WID", &["with"], true),
+    entry("WX", &["weather"], true),
+    entry("YL", &["young lady"], true),
+    entry("+", &["end of message"], false),
+    entry("(", &["over to you only"], false),
+    entry("<KN>", &["over to you only"], false),
+    entry("<SK>", &["end of contact"], false),
+];
+
+/// Résultat de `compress_abbreviations` : le texte abrégé et le temps gagné.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compression {
+    /// Texte abrégé, en majuscules, prêt à être encodé.
+    pub text: String,
+    /// Remplacements effectués, dans l'ordre du texte : le texte en clair et son abréviation.
+    pub substitutions: Vec<(String, String)>,
+    /// Durée du texte d'origine, en points (voir `Morse::duration_of`).
+    pub original_units: f64,
+    /// Durée du texte abrégé, en points.
+    pub compressed_units: f64,
+}
+
+impl Compression {
+    /// Retourne le temps gagné, en points.
+    pub fn saved_units(&self) -> f64 {
+        (self.original_units - self.compressed_units).max(0.0)
+    }
+}
+
+/// Développe les codes Q et les abréviations d'un texte décodé en anglais courant. Les mots
+/// inconnus (indicatifs, prénoms, reports…) sont conservés tels quels.
+///
+/// # Arguments
+///
+/// * `text` - Le texte décodé.
+///
+/// # Exemple
+///
+/// ```rust
+/// use crate::morseus::expand_abbreviations;
+///
+/// fn main() {
+///     assert_eq!(
+///         expand_abbreviations("TNX FER CALL UR RST 599 QTH PARIS"),
+///         "thanks for CALL your signal report 599 my location is PARIS"
+///     );
+///     assert_eq!(expand_abbreviations("qrz?"), "who is calling me?");
+/// }
+/// ```
+pub fn expand_abbreviations(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            let upper: String = word.to_uppercase();
+            ABBREVIATIONS
+                .iter()
+                .find(|entry| entry.abbreviation == upper)
+                .map_or(word, |entry| entry.meanings[0])
+        })
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Remplace les expressions anglaises courantes par leur abréviation télégraphique, pour
+/// raccourcir la transmission. Les expressions les plus longues sont remplacées en premier, et
+/// seulement si l'abréviation est plus courte en Morse. La ponctuation qui suit une expression
+/// est conservée.
+///
+/// Les durées sont mesurées par `Morse::duration_of` avec les réglages actuels (vitesse,
+/// espacement Farnsworth, rapport tiret/point et pondération), et comptées en points.
+///
+/// # Arguments
+///
+/// * `morse` - L'alphabet et le minutage utilisés pour mesurer les durées.
+/// * `text` - Le texte en clair.
+///
+/// # Exemple
+///
+/// ```rust
+/// use crate::morseus::{compress_abbreviations, Compression, Morse};
+///
+/// fn main() {
+///     let morse = Morse::new();
+///     let compression: Compression =
+///         compress_abbreviations(&morse, "Thanks for your report, best regards");
+///     assert_eq!(compression.text, "TNX FER UR RPT, 73");
+///     assert_eq!(compression.substitutions[0], ("Thanks".to_string(), "TNX".to_string()));
+///     assert!(compression.compressed_units < compression.original_units);
+///     assert_eq!(
+///         compression.saved_units(),
+///         compression.original_units - compression.compressed_units
+///     );
+///
+///     // "and" est plus long en Morse que son abréviation "ES"
+///     assert_eq!(compress_abbreviations(&morse, "you and me").text, "YOU ES ME");
+///
+///     // Les durées suivent le minutage de la structure Morse, comme `duration_of`
+///     let mut farnsworth = Morse::new();
+///     farnsworth.set_farnsworth(20.0, 10.0);
+///     let slower: Compression = compress_abbreviations(&farnsworth, "thanks");
+///     assert_eq!(slower.original_units, farnsworth.duration_of("THANKS").units);
+///     assert!(slower.original_units > compress_abbreviations(&morse, "thanks").original_units);
+/// }
+/// ```
+pub fn compress_abbreviations(morse: &Morse, text: &str) -> Compression {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    // Dans le texte, une expression se compare sans casse ni ponctuation finale (sauf '?')
+    let normalized: Vec<String> = words
+        .iter()
+        .map(|word| {
+            word.trim_end_matches(|c: char| ".,;:!".contains(c))
+                .to_lowercase()
+        })
+        .collect();
+    let mut phrases: Vec<(Vec<String>, &str)> = ABBREVIATIONS
+        .iter()
+        .filter(|entry| entry.compress)
+        .flat_map(|entry| {
+            entry.meanings.iter().map(|meaning| {
+                let words: Vec<String> = meaning.split(' ').map(str::to_lowercase).collect();
+                (words, entry.abbreviation)
+            })
+        })
+        .filter(|(phrase, abbreviation)| {
+            morse.duration_of(abbreviation).units
+                < morse.duration_of(&phrase.join(" ").to_uppercase()).units
+        })
+        .collect();
+    phrases.sort_by_key(|(phrase, _)| std::cmp::Reverse(phrase.len()));
+
+    let mut compressed: Vec<String> = Vec::new();
+    let mut substitutions: Vec<(String, String)> = Vec::new();
+    let mut index: usize = 0;
+    while index < words.len() {
+        let matched = phrases.iter().find(|(phrase, _)| {
+            normalized[index..].starts_with(phrase)
+                // La ponctuation ne peut suivre que le dernier mot de l'expression
+                && words[index..index + phrase.len() - 1]
+                    .iter()
+                    .zip(&normalized[index..])
+                    .all(|(word, normalized)| word.to_lowercase() == *normalized)
+        });
+        match matched {
+            Some((phrase, abbreviation)) => {
+                let last: &str = words[index + phrase.len() - 1];
+                let punctuation: &str =
+                    &last[last.trim_end_matches(|c: char| ".,;:!".contains(c)).len()..];
+                let original: String = words[index..index + phrase.len()]
+                    .join(" ")
+                    .trim_end_matches(|c: char| ".,;:!".contains(c))
+                    .to_string();
+                substitutions.push((original, abbreviation.to_string()));
+                compressed.push(format!("{}{}", abbreviation, punctuation));
+                index += phrase.len();
+            }
+            None => {
+                compressed.push(words[index].to_uppercase());
+                index += 1;
+            }
+        }
+    }
+    let text: String = compressed.join(" ");
+    Compression {
+        original_units: morse.duration_of(&words.join(" ").to_uppercase()).units,
+        compressed_units: morse.duration_of(&text).units,
+        text,
+        substitutions,
+    }
+}
@@ -0,0 +1,203 @@
+use hound::Error as HoundError;
+
+use std::io::{Error as IoError, ErrorKind};
+
+use crate::rng::Rng;
+
+/// Seuil à partir duquel le limiteur doux commence à compresser le signal.
+const SOFT_LIMIT_KNEE: f32 = 0.8;
+
+/// Politique appliquée aux échantillons qui sortent de la plage [-1.0, 1.0] lors de l'export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClippingPolicy {
+    /// Écrête brutalement les échantillons à [-1.0, 1.0].
+    HardClip,
+    /// Compresse progressivement les échantillons au-delà de 0.8 pour qu'ils restent dans [-1.0, 1.0].
+    SoftLimit,
+    /// Refuse l'export et retourne une erreur dès qu'un échantillon dépasse la plage.
+    Error,
+}
+
+/// Normalisation du volume appliquée aux échantillons avant l'export.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalization {
+    /// Aucun changement de volume.
+    None,
+    /// Ajuste le gain pour que le pic atteigne le niveau donné (en dBFS).
+    Peak(f32),
+    /// Ajuste le gain pour que le niveau RMS atteigne le niveau donné (en dBFS).
+    Rms(f32),
+}
+
+/// Rapport produit après le rendu des échantillons audio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportReport {
+    /// Gain linéaire appliqué par la normalisation.
+    pub gain: f32,
+    /// Pic absolu des échantillons exportés (entre 0.0 et 1.0).
+    pub peak: f32,
+    /// Pic des échantillons exportés en dBFS.
+    pub peak_dbfs: f32,
+    /// Niveau RMS des échantillons exportés en dBFS.
+    pub rms_dbfs: f32,
+    /// Nombre d'échantillons qui ont dû être écrêtés ou limités.
+    pub clipped_samples: usize,
+}
+
+/// Convertit une amplitude linéaire en dBFS.
+///
+/// # Exemple
+///
+/// ```rust
+/// use crate::morseus::to_dbfs;
+///
+/// fn main() {
+///     assert_eq!(to_dbfs(1.0), 0.0);
+///     assert!((to_dbfs(0.5) + 6.0206).abs() < 0.001);
+/// }
+/// ```
+pub fn to_dbfs(amplitude: f32) -> f32 {
+    20.0 * amplitude.abs().log10()
+}
+
+/// Convertit un niveau en dBFS en amplitude linéaire.
+///
+/// # Exemple
+///
+/// ```rust
+/// use crate::morseus::from_dbfs;
+///
+/// fn main() {
+///     assert_eq!(from_dbfs(0.0), 1.0);
+///     assert!((from_dbfs(-6.0206) - 0.5).abs() < 0.001);
+/// }
+/// ```
+pub fn from_dbfs(dbfs: f32) -> f32 {
+    10f32.powf(dbfs / 20.0)
+}
+
+/// Applique la normalisation puis la politique d'écrêtage aux échantillons.
+pub(crate) fn process_samples(
+    samples: &[f32],
+    normalization: Normalization,
+    clipping_policy: ClippingPolicy,
+) -> Result<(Vec<f32>, ExportReport), HoundError> {
+    let gain: f32 = match normalization {
+        Normalization::None => 1.0,
+        Normalization::Peak(target) => match peak(samples) {
+            p if p > 0.0 => from_dbfs(target) / p,
+            _ => 1.0,
+        },
+        Normalization::Rms(target) => match rms(samples) {
+            r if r > 0.0 => from_dbfs(target) / r,
+            _ => 1.0,
+        },
+    };
+
+    let mut processed: Vec<f32> = Vec::with_capacity(samples.len());
+    let mut meter: LevelMeter = LevelMeter::default();
+    for (index, &sample) in samples.iter().enumerate() {
+        let sample: f32 = sample * gain;
+        let limited: f32 = limit(sample, clipping_policy, index)?;
+        meter.add(sample, limited);
+        processed.push(limited);
+    }
+    Ok((processed, meter.report(gain)))
+}
+
+/// Applique la politique d'écrêtage à un échantillon. `index` sert au message d'erreur.
+pub(crate) fn limit(
+    sample: f32,
+    clipping_policy: ClippingPolicy,
+    index: usize,
+) -> Result<f32, HoundError> {
+    match clipping_policy {
+        ClippingPolicy::HardClip => Ok(sample.clamp(-1.0, 1.0)),
+        ClippingPolicy::SoftLimit => Ok(soft_limit(sample)),
+        ClippingPolicy::Error if sample.abs() > 1.0 => Err(HoundError::IoError(IoError::new(
+            ErrorKind::InvalidData,
+            format!(
+                "l'échantillon {} dépasse la plage [-1.0, 1.0] : {}",
+                index, sample
+            ),
+        ))),
+        ClippingPolicy::Error => Ok(sample),
+    }
+}
+
+/// Mesure au fil de l'eau le pic et le niveau RMS des échantillons écrits.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct LevelMeter {
+    peak: f32,
+    sum_of_squares: f32,
+    count: usize,
+    clipped_samples: usize,
+}
+
+impl LevelMeter {
+    /// Ajoute un échantillon, avant (`sample`) et après (`limited`) écrêtage.
+    pub(crate) fn add(&mut self, sample: f32, limited: f32) {
+        self.peak = self.peak.max(limited.abs());
+        self.sum_of_squares += limited * limited;
+        self.count += 1;
+        if limited != sample {
+            self.clipped_samples += 1;
+        }
+    }
+
+    /// Retourne le rapport des échantillons mesurés, pour un gain de normalisation donné.
+    pub(crate) fn report(&self, gain: f32) -> ExportReport {
+        let rms: f32 = if self.count == 0 {
+            0.0
+        } else {
+            (self.sum_of_squares / self.count as f32).sqrt()
+        };
+        ExportReport {
+            gain,
+            peak: self.peak,
+            peak_dbfs: to_dbfs(self.peak),
+            rms_dbfs: to_dbfs(rms),
+            clipped_samples: self.clipped_samples,
+        }
+    }
+}
+
+/// Convertit un échantillon flottant en entier signé sur `bits_per_sample` bits,
+/// en ajoutant un bruit de dither triangulaire (TPDF) si un générateur est fourni.
+pub(crate) fn quantize(sample: f32, bits_per_sample: u16, dither: Option<&mut Rng>) -> i32 {
+    let scale: f32 = ((1i64 << (bits_per_sample - 1)) - 1) as f32;
+    let noise: f32 = match dither {
+        // Somme de deux bruits uniformes : distribution triangulaire sur ±1 LSB
+        Some(rng) => rng.next_f32() - rng.next_f32(),
+        None => 0.0,
+    };
+    (sample * scale + noise).round().clamp(-scale - 1.0, scale) as i32
+}
+
+/// Compresse les échantillons au-delà de `SOFT_LIMIT_KNEE` pour qu'ils tendent vers ±1.0.
+fn soft_limit(sample: f32) -> f32 {
+    let magnitude: f32 = sample.abs();
+    if magnitude <= SOFT_LIMIT_KNEE {
+        return sample;
+    }
+    let headroom: f32 = 1.0 - SOFT_LIMIT_KNEE;
+    let limited: f32 =
+        SOFT_LIMIT_KNEE + headroom * ((magnitude - SOFT_LIMIT_KNEE) / headroom).tanh();
+    limited.copysign(sample)
+}
+
+/// Retourne le pic absolu des échantillons.
+fn peak(samples: &[f32]) -> f32 {
+    samples
+        .iter()
+        .fold(0.0, |max, sample| max.max(sample.abs()))
+}
+
+/// Retourne le niveau RMS des échantillons.
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = samples.iter().map(|sample| sample * sample).sum();
+    (sum / samples.len() as f32).sqrt()
+}
@@ -0,0 +1,494 @@
+use hound::{Error as HoundError, SampleFormat, WavReader};
+
+use std::{io::Read, time::Duration};
+
+use crate::{
+    spectrum::power_spectrum,
+    timing::{dit_to_wpm, DecodedCharacter, TimingTracker},
+};
+
+/// Fréquences entre lesquelles une tonalité est recherchée, en Hz.
+pub(crate) const TONE_RANGE: (f32, f32) = (100.0, 3000.0);
+
+/// Rapport signal sur bruit minimal (en dB) pour qu'une tonalité détectée soit utilisée.
+pub(crate) const MIN_TONE_SNR: f32 = 10.0;
+
+/// Méthode utilisée par le décodeur audio pour choisir les fréquences à écouter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneDetection {
+    /// Écoute les fréquences du bip court et du bip long configurées.
+    Fixed,
+    /// Recherche la tonalité dominante dans le signal et l'écoute.
+    Automatic,
+    /// Recherche la tonalité dominante puis suit sa dérive lente pendant le message.
+    Tracking,
+}
+
+/// Tonalité détectée dans un signal audio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DetectedTone {
+    /// Fréquence de la tonalité, en Hz. En mode `ToneDetection::Tracking`, il s'agit de la
+    /// fréquence suivie à la fin du message.
+    pub frequency: f32,
+    /// Rapport signal sur bruit, en dB : puissance du pic par rapport au niveau médian du
+    /// spectre.
+    pub snr: f32,
+}
+
+/// Recherche la tonalité dominante d'un signal entre 100 et 3000 Hz.
+///
+/// Les spectres de puissance de blocs d'environ 100 ms sont additionnés, puis la fréquence
+/// du pic le plus fort est affinée par interpolation parabolique. Retourne `None` si le
+/// signal ne contient aucune énergie dans cette plage.
+pub(crate) fn detect_tone(samples: &[f32], sampling_rate: u32) -> Option<DetectedTone> {
+    if samples.is_empty() || sampling_rate == 0 {
+        return None;
+    }
+    let frame_length: usize = ((sampling_rate / 10) as usize).max(2).next_power_of_two();
+    let mut spectrum: Vec<f32> = vec![0.0; frame_length / 2 + 1];
+    for frame in samples.chunks(frame_length) {
+        let mut padded: Vec<f32> = frame.to_vec();
+        padded.resize(frame_length, 0.0);
+        for (total, power) in spectrum.iter_mut().zip(power_spectrum(&padded)) {
+            *total += power;
+        }
+    }
+    let resolution: f32 = sampling_rate as f32 / frame_length as f32;
+    let lowest: usize = ((TONE_RANGE.0 / resolution).ceil() as usize).max(1);
+    let highest: usize = ((TONE_RANGE.1 / resolution) as usize).min(spectrum.len() - 2);
+    if lowest > highest {
+        return None;
+    }
+    let (peak, peak_power) = (lowest..=highest)
+        .map(|bin| (bin, spectrum[bin]))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    if peak_power <= 0.0 {
+        return None;
+    }
+    let mut sorted: Vec<f32> = spectrum[lowest..=highest].to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let noise: f32 = sorted[sorted.len() / 2].max(f32::MIN_POSITIVE);
+    // Interpolation parabolique sur le logarithme de la puissance des bandes voisines
+    let (before, at, after) = (
+        spectrum[peak - 1].max(f32::MIN_POSITIVE).ln(),
+        peak_power.ln(),
+        spectrum[peak + 1].max(f32::MIN_POSITIVE).ln(),
+    );
+    let curvature: f32 = before - 2.0 * at + after;
+    let offset: f32 = if curvature < 0.0 {
+        (0.5 * (before - after) / curvature).clamp(-0.5, 0.5)
+    } else {
+        0.0
+    };
+    Some(DetectedTone {
+        frequency: (peak as f32 + offset) * resolution,
+        snr: 10.0 * (peak_power / noise).log10(),
+    })
+}
+
+/// Mesure l'amplitude d'une fréquence bloc après bloc, en suivant éventuellement sa dérive.
+#[derive(Debug, Clone)]
+pub(crate) struct ToneTracker {
+    frequency: f32,
+    tracking: bool,
+    peak: f32,
+}
+
+impl ToneTracker {
+    /// Facteur de correction de la fréquence appliqué à chaque bloc.
+    const DRIFT_RATE: f32 = 0.05;
+    /// Décroissance par bloc du niveau de crête mémorisé.
+    const PEAK_DECAY: f32 = 0.995;
+
+    /// Crée un suivi de la fréquence `frequency`, fixe si `tracking` est faux.
+    pub(crate) fn new(frequency: f32, tracking: bool) -> Self {
+        ToneTracker {
+            frequency,
+            tracking,
+            peak: 0.0,
+        }
+    }
+
+    /// Retourne la fréquence suivie.
+    pub(crate) fn frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    /// Mesure l'amplitude de la fréquence suivie dans un bloc, puis corrige la fréquence si le
+    /// bloc contient un signal.
+    ///
+    /// La correction compare l'amplitude à une demi-bande de part et d'autre de la fréquence
+    /// (interpolation parabolique) et n'en applique qu'une petite fraction, pour ne suivre que
+    /// les dérives lentes.
+    pub(crate) fn level(&mut self, block: &[f32], sampling_rate: u32) -> f32 {
+        let level: f32 = goertzel(block, self.frequency, sampling_rate);
+        if !self.tracking || block.is_empty() {
+            return level;
+        }
+        self.peak = level.max(self.peak * Self::PEAK_DECAY);
+        if level > 0.5 * self.peak {
+            let half_band: f32 = sampling_rate as f32 / block.len() as f32 / 2.0;
+            let below: f32 = goertzel(block, self.frequency - half_band, sampling_rate);
+            let above: f32 = goertzel(block, self.frequency + half_band, sampling_rate);
+            let curvature: f32 = below - 2.0 * level + above;
+            if curvature < 0.0 {
+                let offset: f32 = (0.5 * (below - above) / curvature).clamp(-1.0, 1.0);
+                self.frequency += Self::DRIFT_RATE * offset * half_band;
+            }
+        }
+        level
+    }
+}
+
+/// Lit tous les échantillons d'un fichier WAV, quel que soit son format, et les convertit en
+/// flottants entre -1.0 et 1.0. Les canaux multiples sont mélangés en un seul.
+pub(crate) fn read_wav<R: Read>(reader: WavReader<R>) -> Result<(Vec<f32>, u32), HoundError> {
+    let spec = reader.spec();
+    let channels: usize = spec.channels.max(1) as usize;
+    let interleaved: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>()?,
+        SampleFormat::Int => {
+            let scale: f32 = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|sample| sample.map(|sample| sample as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+    let samples: Vec<f32> = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    Ok((samples, spec.sample_rate))
+}
+
+/// Calcule l'amplitude de la composante de fréquence `frequency` dans un bloc d'échantillons
+/// avec l'algorithme de Goertzel.
+pub(crate) fn goertzel(block: &[f32], frequency: f32, sampling_rate: u32) -> f32 {
+    if block.is_empty() {
+        return 0.0;
+    }
+    let omega: f32 = 2.0 * std::f32::consts::PI * frequency / sampling_rate as f32;
+    let coefficient: f32 = 2.0 * omega.cos();
+    let (mut previous, mut before_previous) = (0.0f32, 0.0f32);
+    for &sample in block {
+        let current: f32 = sample + coefficient * previous - before_previous;
+        before_previous = previous;
+        previous = current;
+    }
+    let power: f32 = previous * previous + before_previous * before_previous
+        - coefficient * previous * before_previous;
+    2.0 * power.max(0.0).sqrt() / block.len() as f32
+}
+
+/// Calcule l'enveloppe du signal : pour chaque bloc de `block_length` échantillons, l'amplitude
+/// la plus forte parmi les fréquences suivies.
+pub(crate) fn envelope(
+    samples: &[f32],
+    trackers: &mut [ToneTracker],
+    sampling_rate: u32,
+    block_length: usize,
+) -> Vec<f32> {
+    samples
+        .chunks(block_length.max(1))
+        .map(|block| {
+            trackers
+                .iter_mut()
+                .map(|tracker| tracker.level(block, sampling_rate))
+                .fold(0.0, f32::max)
+        })
+        .collect()
+}
+
+/// Retourne la durée de `count` échantillons, nulle si le taux d'échantillonnage est nul.
+fn samples_duration(count: usize, sampling_rate: u32) -> Duration {
+    if sampling_rate == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs_f64(count as f64 / sampling_rate as f64)
+}
+
+/// Convertit une enveloppe en durées de manipulation (`true` pour un signal, `false` pour un
+/// silence).
+///
+/// Le seuil est placé à mi-chemin entre le niveau du bruit (10e centile) et celui du signal
+/// (99e centile), ce qui l'adapte au volume de l'enregistrement. Une enveloppe sans signal
+/// ne produit aucune durée.
+pub(crate) fn key_runs(envelope: &[f32], block_duration: Duration) -> Vec<(bool, Duration)> {
+    let mut sorted: Vec<f32> = envelope.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let percentile = |p: f32| -> f32 {
+        sorted
+            .get(((sorted.len() as f32 - 1.0) * p) as usize)
+            .copied()
+            .unwrap_or(0.0)
+    };
+    let (noise, signal) = (percentile(0.10), percentile(0.99));
+    if signal - noise < 1e-3 {
+        return Vec::new();
+    }
+    let threshold: f32 = noise + (signal - noise) / 2.0;
+
+    let mut runs: Vec<(bool, Duration)> = Vec::new();
+    for &level in envelope {
+        let key_down: bool = level > threshold;
+        match runs.last_mut() {
+            Some((last, duration)) if *last == key_down => *duration += block_duration,
+            _ => runs.push((key_down, block_duration)),
+        }
+    }
+    runs
+}
+
+/// Détecteur de l'état de la clé à partir du niveau d'un signal, bloc après bloc.
+///
+/// Le seuil entre signal et silence suit en continu le niveau du bruit et celui du signal,
+/// avec une hystérésis et un anti-rebond pour ignorer les fluctuations.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyDetector {
+    rate: f32,
+    signal: f32,
+    noise: f32,
+    key_down: bool,
+    pending_blocks: usize,
+}
+
+impl KeyDetector {
+    /// Durée caractéristique du suivi des niveaux de signal et de bruit, en secondes.
+    const LEVEL_TRACKING: f32 = 3.0;
+    /// Nombre de blocs consécutifs nécessaires pour changer l'état de la clé.
+    const DEBOUNCE_BLOCKS: usize = 2;
+
+    /// Crée un détecteur pour des blocs de durée `block_duration`.
+    pub(crate) fn new(block_duration: Duration) -> Self {
+        KeyDetector {
+            rate: (block_duration.as_secs_f32() / Self::LEVEL_TRACKING).min(1.0),
+            signal: 0.0,
+            noise: 0.0,
+            key_down: false,
+            pending_blocks: 0,
+        }
+    }
+
+    /// Rapport entre le niveau du signal et celui du bruit suivis, en dB.
+    pub(crate) fn snr(&self) -> f32 {
+        20.0 * (self.signal / self.noise.max(f32::MIN_POSITIVE)).log10()
+    }
+
+    /// Met à jour les niveaux suivis avec le niveau d'un nouveau bloc et retourne l'état de la
+    /// clé. Si `allowed` est faux, le bloc est traité comme un silence et ne peut pas relever
+    /// le niveau du signal.
+    pub(crate) fn update(&mut self, level: f32, allowed: bool) -> bool {
+        // Montée immédiate et descente lente pour le signal, l'inverse pour le bruit
+        if allowed && level > self.signal {
+            self.signal = level;
+        } else if level < self.signal {
+            self.signal += (level - self.signal) * self.rate;
+        }
+        if level < self.noise {
+            self.noise = level;
+        } else {
+            self.noise += (level - self.noise) * self.rate;
+        }
+        // Il faut au moins 6 dB d'écart entre le signal et le bruit pour reconnaître un signal
+        let contrast: f32 = self.signal - self.noise;
+        let key_down: bool = if !allowed || contrast < 1e-3 || self.signal < 2.0 * self.noise {
+            false
+        } else if self.key_down {
+            level > self.noise + 0.4 * contrast
+        } else {
+            level > self.noise + 0.6 * contrast
+        };
+        // L'état ne change qu'après plusieurs blocs consécutifs, pour ignorer les rebonds ; les
+        // deux fronts étant retardés d'autant, les durées sont conservées
+        if key_down == self.key_down {
+            self.pending_blocks = 0;
+        } else {
+            self.pending_blocks += 1;
+            if self.pending_blocks >= Self::DEBOUNCE_BLOCKS {
+                self.pending_blocks = 0;
+                self.key_down = key_down;
+            }
+        }
+        self.key_down
+    }
+}
+
+/// Décodeur audio incrémental.
+///
+/// Les échantillons sont reçus par morceaux de taille quelconque (fichier en cours d'écriture,
+/// tube, carte son…) et l'état est conservé d'un morceau à l'autre. Le seuil entre signal et
+/// silence suit en continu le niveau du bruit et celui du signal, avec une hystérésis pour
+/// éviter les rebonds. Chaque caractère est émis dès que le silence qui le suit dépasse
+/// 2 points.
+///
+/// Avec la détection automatique de la tonalité, les échantillons sont mis de côté jusqu'à ce
+/// qu'une tonalité soit trouvée dans la dernière seconde reçue, puis décodés d'un coup.
+#[derive(Debug, Clone)]
+pub struct StreamingAudioDecoder {
+    trackers: Vec<ToneTracker>,
+    detection: ToneDetection,
+    acquisition: Option<Vec<f32>>,
+    tone: Option<DetectedTone>,
+    sampling_rate: u32,
+    block: Vec<f32>,
+    block_length: usize,
+    detector: KeyDetector,
+    timing: TimingTracker,
+}
+
+impl StreamingAudioDecoder {
+    /// Durée des échantillons analysés pour rechercher la tonalité, en secondes.
+    const ACQUISITION_WINDOW: f32 = 1.0;
+
+    pub(crate) fn new(
+        frequencies: Vec<f32>,
+        detection: ToneDetection,
+        sampling_rate: u32,
+        block_duration: Duration,
+        timing: TimingTracker,
+    ) -> Self {
+        let block_length: usize =
+            ((block_duration.as_secs_f64() * sampling_rate as f64).round() as usize).max(1);
+        StreamingAudioDecoder {
+            trackers: frequencies
+                .into_iter()
+                .map(|frequency| ToneTracker::new(frequency, false))
+                .collect(),
+            detection,
+            acquisition: match detection {
+                ToneDetection::Fixed => None,
+                ToneDetection::Automatic | ToneDetection::Tracking => Some(Vec::new()),
+            },
+            tone: None,
+            sampling_rate,
+            block: Vec::with_capacity(block_length),
+            block_length,
+            detector: KeyDetector::new(samples_duration(block_length, sampling_rate)),
+            timing,
+        }
+    }
+
+    /// Ajoute des échantillons et retourne les caractères décodés depuis le dernier appel.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Les nouveaux échantillons, au taux d'échantillonnage du décodeur. Ils sont
+    ///   ignorés si ce taux est nul.
+    pub fn push(&mut self, samples: &[f32]) -> Vec<DecodedCharacter> {
+        if self.sampling_rate == 0 {
+            return Vec::new();
+        }
+        match self.acquisition.as_mut() {
+            Some(buffer) => {
+                buffer.extend_from_slice(samples);
+                self.acquire(false)
+            }
+            None => self.process(samples),
+        }
+    }
+
+    /// Termine le flux et retourne les derniers caractères décodés.
+    pub fn finish(&mut self) -> Vec<DecodedCharacter> {
+        let mut decoded: Vec<DecodedCharacter> = self.acquire(true);
+        if !self.block.is_empty() {
+            decoded.extend(self.process_block());
+            self.block.clear();
+        }
+        decoded.extend(self.timing.finish());
+        decoded
+    }
+
+    /// Retourne la durée estimée d'un point.
+    pub fn dit(&self) -> Duration {
+        self.timing.dit()
+    }
+
+    /// Retourne la vitesse estimée en mots par minute.
+    pub fn wpm(&self) -> f32 {
+        dit_to_wpm(self.timing.dit())
+    }
+
+    /// Retourne la tonalité détectée, si la détection automatique est activée et qu'une
+    /// tonalité a été trouvée. En mode `ToneDetection::Tracking`, la fréquence est celle
+    /// suivie actuellement.
+    pub fn tone(&self) -> Option<DetectedTone> {
+        self.tone.map(|tone| DetectedTone {
+            frequency: self.trackers[0].frequency(),
+            ..tone
+        })
+    }
+
+    /// Retourne la durée totale des échantillons décodés (sans ceux mis de côté pendant la
+    /// recherche de la tonalité).
+    pub fn time(&self) -> Duration {
+        self.timing.time()
+    }
+
+    /// Retourne le taux d'échantillonnage attendu.
+    pub fn sampling_rate(&self) -> u32 {
+        self.sampling_rate
+    }
+
+    /// Recherche la tonalité dans les échantillons mis de côté, et les décode dès qu'elle est
+    /// trouvée. Sans tonalité, les échantillons les plus anciens sont comptés comme du silence.
+    fn acquire(&mut self, finished: bool) -> Vec<DecodedCharacter> {
+        let mut decoded: Vec<DecodedCharacter> = Vec::new();
+        let window: usize =
+            ((Self::ACQUISITION_WINDOW * self.sampling_rate as f32) as usize).max(1);
+        while let Some(buffer) = self.acquisition.as_mut() {
+            if buffer.len() < window && !finished {
+                break;
+            }
+            if let Some(tone) =
+                detect_tone(buffer, self.sampling_rate).filter(|tone| tone.snr >= MIN_TONE_SNR)
+            {
+                let buffer: Vec<f32> = self.acquisition.take().unwrap_or_default();
+                self.tone = Some(tone);
+                self.trackers = vec![ToneTracker::new(
+                    tone.frequency,
+                    self.detection == ToneDetection::Tracking,
+                )];
+                decoded.extend(self.process(&buffer));
+                break;
+            }
+            let dropped: usize = if finished {
+                buffer.len()
+            } else {
+                buffer.len() / 2
+            };
+            buffer.drain(..dropped);
+            let elapsed: Duration = samples_duration(dropped, self.sampling_rate);
+            decoded.extend(self.timing.update(false, elapsed));
+            if finished {
+                break;
+            }
+        }
+        decoded
+    }
+
+    /// Découpe les échantillons en blocs et les décode.
+    fn process(&mut self, samples: &[f32]) -> Vec<DecodedCharacter> {
+        let mut decoded: Vec<DecodedCharacter> = Vec::new();
+        for &sample in samples {
+            self.block.push(sample);
+            if self.block.len() == self.block_length {
+                decoded.extend(self.process_block());
+                self.block.clear();
+            }
+        }
+        decoded
+    }
+
+    /// Mesure le niveau du bloc courant, met à jour les niveaux suivis et l'état de la clé.
+    fn process_block(&mut self) -> Vec<DecodedCharacter> {
+        let level: f32 = self
+            .trackers
+            .iter_mut()
+            .map(|tracker| tracker.level(&self.block, self.sampling_rate))
+            .fold(0.0, f32::max);
+        let key_down: bool = self.detector.update(level, true);
+        let elapsed: Duration = samples_duration(self.block.len(), self.sampling_rate);
+        self.timing.update(key_down, elapsed)
+    }
+}
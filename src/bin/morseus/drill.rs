@@ -0,0 +1,195 @@
+//! Exercice de reconnaissance instantanée des caractères (ICR) : un caractère est joué, et
+//! l'élève tape le caractère reconnu le plus vite possible.
+
+use std::{
+    error::Error,
+    io::{self, Write},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use morseus::{AudioSink, Element, IcrDrill, IcrPrompt, IcrStatistics, Morse, RodioSink};
+
+use crate::{
+    options::Notation,
+    terminal::{self, Input, Key, KeyAction, RawTerminal},
+};
+
+/// Silence entre la réponse et le caractère suivant.
+const GAP: Duration = Duration::from_millis(700);
+
+/// Nombre de caractères les plus lents affichés à la fin de la séance.
+const SLOWEST: usize = 5;
+
+/// Événement reçu du terminal.
+struct Event(Vec<u8>, Instant);
+
+/// Destination qui note l'instant où le premier échantillon lui est confié.
+struct Timestamped<'a, S> {
+    sink: &'a mut S,
+    first: Option<Instant>,
+}
+
+impl<S: AudioSink> AudioSink for Timestamped<'_, S> {
+    type Error = S::Error;
+
+    fn write(
+        &mut self,
+        element: &Element,
+        samples: &[f32],
+        sampling_rate: u32,
+    ) -> Result<(), S::Error> {
+        self.first.get_or_insert_with(Instant::now);
+        self.sink.write(element, samples, sampling_rate)
+    }
+
+    fn finish(&mut self) -> Result<(), S::Error> {
+        self.sink.finish()
+    }
+}
+
+/// Réponse de l'élève à une question.
+enum Answer {
+    Character(char, Instant),
+    Timeout,
+    Quit,
+}
+
+/// Lance un exercice ICR et retourne ses résultats.
+pub(crate) fn run(
+    morse: &Morse,
+    mut drill: IcrDrill,
+    notation: Notation,
+) -> Result<IcrDrill, Box<dyn Error>> {
+    let mut sink: RodioSink =
+        RodioSink::try_default().map_err(|error| format!("dispositif audio : {}", error))?;
+    let terminal: RawTerminal = RawTerminal::enable()?;
+    let (sender, receiver) = channel();
+    terminal::spawn_reader(sender, Event);
+
+    let mut output = io::stdout();
+    write!(
+        output,
+        "Tapez chaque caractère dès que vous le reconnaissez. Échap : arrêter.\r\n\r\n"
+    )?;
+    output.flush()?;
+    let start: Instant = Instant::now();
+    loop {
+        thread::sleep(GAP);
+        // Les touches tapées entre deux questions ne comptent pas
+        if drain(&receiver) {
+            break;
+        }
+        let Some(prompt) = drill.next_prompt(morse, start.elapsed()) else {
+            break;
+        };
+        let mut timestamped = Timestamped {
+            sink: &mut sink,
+            first: None,
+        };
+        let _ = morse.play_morse_code_on(&prompt.code, &mut timestamped);
+        // Le temps de réaction part du moment où l'audio a réellement été confié au
+        // dispositif, pas de celui où la question a été tirée
+        let prompt: IcrPrompt = match timestamped.first {
+            Some(first) => drill
+                .set_prompt_start(first.saturating_duration_since(start))
+                .unwrap_or(prompt),
+            None => prompt,
+        };
+        let deadline: Instant = start + prompt.ended + drill.timeout();
+        let line: String = match answer(&receiver, deadline) {
+            Answer::Character(character, at) => {
+                let Some(trial) = drill.answer(character, at.saturating_duration_since(start))
+                else {
+                    continue;
+                };
+                match (trial.is_correct(), trial.reaction_time) {
+                    (true, Some(time)) => format!("{} ms", time.as_millis()),
+                    (false, Some(_)) => format!("✗ {}", trial.answer.unwrap_or(character)),
+                    (_, None) => "trop tard".to_string(),
+                }
+            }
+            Answer::Timeout => "manqué".to_string(),
+            Answer::Quit => break,
+        };
+        write!(output, "{}", describe(&prompt, notation, &line))?;
+        output.flush()?;
+    }
+    drop(terminal);
+    Ok(drill)
+}
+
+/// Attend la première touche tapée avant `deadline`.
+fn answer(receiver: &Receiver<Event>, deadline: Instant) -> Answer {
+    loop {
+        let remaining: Duration = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(remaining) {
+            Ok(Event(bytes, at)) => {
+                for input in terminal::parse(&bytes) {
+                    match input {
+                        Input::Key(Key::Escape | Key::Interrupt, KeyAction::Press) => {
+                            return Answer::Quit
+                        }
+                        Input::Key(Key::Character(character), KeyAction::Press) => {
+                            return Answer::Character(character, at)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => return Answer::Timeout,
+            Err(RecvTimeoutError::Disconnected) => return Answer::Quit,
+        }
+    }
+}
+
+/// Vide les événements en attente. Retourne `true` si l'élève a demandé à arrêter.
+fn drain(receiver: &Receiver<Event>) -> bool {
+    while let Ok(Event(bytes, _)) = receiver.try_recv() {
+        if terminal::parse(&bytes).iter().any(|input| {
+            matches!(
+                input,
+                Input::Key(Key::Escape | Key::Interrupt, KeyAction::Press)
+            )
+        }) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Ligne affichée après chaque question : le caractère joué, son code et le résultat.
+fn describe(prompt: &IcrPrompt, notation: Notation, result: &str) -> String {
+    format!(
+        "{} {:<8} {}\r\n",
+        prompt.character,
+        notation.format(&prompt.code),
+        result
+    )
+}
+
+/// Résumé de la séance : précision, temps de réaction moyen et caractères les plus lents.
+pub(crate) fn summary(statistics: &IcrStatistics) -> String {
+    let mut summary: String = format!(
+        "{}/{} bonnes réponses ({:.0} %)",
+        statistics.correct,
+        statistics.attempts,
+        statistics.accuracy() * 100.0
+    );
+    if let Some(time) = statistics.mean_reaction_time {
+        summary.push_str(&format!(
+            ", temps de réaction moyen : {} ms",
+            time.as_millis()
+        ));
+    }
+    let slowest: Vec<String> = statistics
+        .slowest(SLOWEST)
+        .into_iter()
+        .map(|character| character.to_string())
+        .collect();
+    if !slowest.is_empty() {
+        summary.push_str(&format!("\nles plus lents : {}", slowest.join(" ")));
+    }
+    summary
+}
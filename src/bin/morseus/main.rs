@@ -0,0 +1,322 @@
+//! Outil en ligne de commande `morseus` : encode, décode, traduit, joue, exporte et écoute du
+//! code Morse. Les sous-commandes lisent leurs arguments ou l'entrée standard et écrivent sur
+//! la sortie standard, pour s'insérer dans des pipelines shell.
+
+// Le mode brut du terminal passe par `stty` : les commandes interactives sont réservées à Unix
+#[cfg(all(feature = "playback", unix))]
+mod drill;
+mod options;
+#[cfg(all(feature = "playback", unix))]
+mod repl;
+#[cfg(all(feature = "playback", unix))]
+mod terminal;
+
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufRead, Read, Write},
+    process::ExitCode,
+};
+
+#[cfg(all(feature = "playback", unix))]
+use morseus::IcrDrill;
+use morseus::{Morse, ToneDetection};
+
+use options::{Command, Notation, Options, UsageError, DEFAULT_TONE, DEFAULT_WPM, USAGE};
+
+fn main() -> ExitCode {
+    let options: Options = match options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("morseus : {}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        // La sortie a été fermée (par exemple par `head`) : rien de plus à écrire
+        Err(error)
+            if error
+                .downcast_ref::<io::Error>()
+                .is_some_and(|error| error.kind() == io::ErrorKind::BrokenPipe) =>
+        {
+            ExitCode::SUCCESS
+        }
+        Err(error) if error.is::<UsageError>() => {
+            eprintln!("morseus : {}\n\n{}", error, USAGE);
+            ExitCode::from(2)
+        }
+        Err(error) => {
+            eprintln!("morseus : {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl Error for UsageError {}
+
+/// Exécute la sous-commande demandée.
+fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let mut morse: Morse = Morse::new();
+    if let Some(alphabet) = &options.alphabet {
+        load_alphabet(&mut morse, alphabet)?;
+    }
+    let notation: Notation = options.notation;
+    let mut output = io::stdout().lock();
+
+    match options.command {
+        Command::Help => write!(output, "{}", USAGE)?,
+        Command::Encode => for_each_line(options, &mut output, |line| {
+            notation.format(&morse.encode(&line.to_uppercase()))
+        })?,
+        Command::Decode => for_each_line(options, &mut output, |line| {
+            morse.decode(&notation.normalize(line))
+        })?,
+        Command::Translate => for_each_line(options, &mut output, |line| {
+            let words: Vec<String> = line
+                .to_uppercase()
+                .split(' ')
+                .map(|word| {
+                    let word: String = notation.normalize(word);
+                    let translated: String = morse.translate(&word);
+                    if morse.is_morse(&word) {
+                        translated
+                    } else {
+                        notation.format(&translated)
+                    }
+                })
+                .collect();
+            words.join(" ")
+        })?,
+        Command::Play => {
+            set_timing(&mut morse, options);
+            let message: String = message(options)?;
+            play(&morse, &message)?;
+        }
+        Command::Wav => {
+            set_timing(&mut morse, options);
+            let message: String = message(options)?;
+            let filename: &str = options.output.as_deref().unwrap_or("morse.wav");
+            if morse.is_morse(&message) {
+                morse.to_audio(&message, filename)?;
+            } else {
+                morse.text_to_audio(&message, filename)?;
+            }
+        }
+        Command::Repl => repl(&morse, options)?,
+        Command::Drill => drill(&morse, options, &mut output)?,
+        Command::Listen => {
+            let [filename] = options.arguments.as_slice() else {
+                return Err(UsageError("listen attend un seul fichier WAV".to_string()).into());
+            };
+            if options.wpm.is_some() || options.farnsworth.is_some() {
+                // Vitesse attendue : le décodeur s'adapte ensuite à la vitesse réelle
+                set_timing(&mut morse, options);
+            }
+            match options.tone {
+                Some(tone) => morse.set_tone(tone),
+                None => morse.set_tone_detection(ToneDetection::Tracking),
+            }
+            let decoded = morse.decode_audio(filename)?;
+            writeln!(output, "{}", decoded.text)?;
+            if options.verbose {
+                eprint!("vitesse : {:.1} wpm", decoded.wpm);
+                if let Some(tone) = decoded.tone {
+                    eprint!(", tonalité : {:.0} Hz ({:.0} dB)", tone.frequency, tone.snr);
+                }
+                eprintln!();
+            }
+        }
+    }
+    output.flush()?;
+    Ok(())
+}
+
+/// Applique chaque ligne des arguments, ou de l'entrée standard en leur absence, à `convert`
+/// et écrit le résultat au fur et à mesure.
+fn for_each_line<W, F>(options: &Options, output: &mut W, mut convert: F) -> io::Result<()>
+where
+    W: Write,
+    F: FnMut(&str) -> String,
+{
+    if !options.arguments.is_empty() {
+        return writeln!(output, "{}", convert(&options.arguments.join(" ")));
+    }
+    for line in io::stdin().lock().lines() {
+        writeln!(output, "{}", convert(&line?))?;
+        // Chaque ligne est transmise sans attendre la fin de l'entrée
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// Retourne le message à jouer ou à exporter : les arguments, ou toute l'entrée standard, en
+/// notation ASCII et en majuscules.
+fn message(options: &Options) -> Result<String, Box<dyn Error>> {
+    let message: String = if options.arguments.is_empty() {
+        let mut input: String = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        input.split_whitespace().collect::<Vec<&str>>().join(" ")
+    } else {
+        options.arguments.join(" ")
+    };
+    Ok(options.notation.normalize(&message).to_uppercase())
+}
+
+/// Règle la vitesse, la pondération et la tonalité utilisées pour jouer ou exporter un
+/// message.
+fn set_timing(morse: &mut Morse, options: &Options) {
+    if let Some(dah_ratio) = options.dah_ratio {
+        morse.set_dah_ratio(dah_ratio);
+    }
+    if let Some(weight) = options.weight {
+        morse.set_weight(weight);
+    }
+    let wpm: f32 = options.wpm.unwrap_or(DEFAULT_WPM);
+    match options.farnsworth {
+        Some(effective_wpm) => morse.set_farnsworth(wpm, effective_wpm),
+        None => morse.set_wpm(wpm),
+    }
+    morse.set_tone(options.tone.unwrap_or(DEFAULT_TONE));
+}
+
+/// Joue un texte ou un code Morse et attend la fin de la lecture.
+#[cfg(feature = "playback")]
+fn play(morse: &Morse, message: &str) -> Result<(), Box<dyn Error>> {
+    let player = if morse.is_morse(message) {
+        morse.play(message)?
+    } else {
+        morse.play_text(message)?
+    };
+    player.wait();
+    Ok(())
+}
+
+#[cfg(not(feature = "playback"))]
+fn play(_morse: &Morse, _message: &str) -> Result<(), Box<dyn Error>> {
+    Err("lecture indisponible : compilé sans la fonctionnalité `playback`".into())
+}
+
+/// Lance le mode interactif.
+#[cfg(all(feature = "playback", unix))]
+fn repl(morse: &Morse, options: &Options) -> Result<(), Box<dyn Error>> {
+    let mut morse: Morse = morse.clone();
+    set_timing(&mut morse, options);
+    repl::run(
+        morse,
+        options.wpm.unwrap_or(DEFAULT_WPM),
+        options.farnsworth,
+        options.notation,
+    )
+}
+
+#[cfg(not(feature = "playback"))]
+fn repl(_morse: &Morse, _options: &Options) -> Result<(), Box<dyn Error>> {
+    Err("mode interactif indisponible : compilé sans la fonctionnalité `playback`".into())
+}
+
+#[cfg(all(feature = "playback", not(unix)))]
+fn repl(_morse: &Morse, _options: &Options) -> Result<(), Box<dyn Error>> {
+    Err("mode interactif indisponible : il nécessite un terminal Unix".into())
+}
+
+/// Lance un exercice d'entraînement et affiche son résumé.
+#[cfg(all(feature = "playback", unix))]
+fn drill<W: Write>(morse: &Morse, options: &Options, output: &mut W) -> Result<(), Box<dyn Error>> {
+    let (exercise, characters) = match options.arguments.as_slice() {
+        [exercise] => (exercise, "ABCDEFGHIJKLMNOPQRSTUVWXYZ".to_string()),
+        [exercise, characters] => (exercise, characters.to_uppercase()),
+        _ => return Err(UsageError("drill attend un exercice : icr".to_string()).into()),
+    };
+    if exercise != "icr" {
+        return Err(UsageError(format!("exercice inconnu : {} (icr)", exercise)).into());
+    }
+    let characters: Vec<char> = characters.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some(character) = characters
+        .iter()
+        .find(|character| morse.get_morse_code(**character).is_none())
+    {
+        return Err(format!("« {} » n'a pas de code Morse", character).into());
+    }
+    let mut morse: Morse = morse.clone();
+    set_timing(&mut morse, options);
+    let mut icr: IcrDrill = IcrDrill::new(&characters);
+    if let Some(count) = options.count {
+        icr.set_length(count);
+    }
+    if let Some(seed) = options.seed {
+        icr.set_seed(seed);
+    }
+    let icr: IcrDrill = drill::run(&morse, icr, options.notation)?;
+    writeln!(output, "{}", drill::summary(&icr.statistics()))?;
+    if let Some(filename) = &options.output {
+        icr.save(filename)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "playback"))]
+fn drill<W: Write>(
+    _morse: &Morse,
+    _options: &Options,
+    _output: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    Err("exercices indisponibles : compilés sans la fonctionnalité `playback`".into())
+}
+
+#[cfg(all(feature = "playback", not(unix)))]
+fn drill<W: Write>(
+    _morse: &Morse,
+    _options: &Options,
+    _output: &mut W,
+) -> Result<(), Box<dyn Error>> {
+    Err("exercices indisponibles : ils nécessitent un terminal Unix".into())
+}
+
+/// Charge un alphabet : `international`, ou un fichier dont chaque ligne associe un caractère
+/// à son code (les lignes vides et celles qui commencent par `#` sont ignorées).
+fn load_alphabet(morse: &mut Morse, alphabet: &str) -> Result<(), Box<dyn Error>> {
+    if alphabet == "international" {
+        return Ok(());
+    }
+    let content: String = fs::read_to_string(alphabet)
+        .map_err(|error| format!("alphabet {} : {}", alphabet, error))?;
+    for (number, line) in content.lines().enumerate() {
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let (Some(character), Some(code), None) = (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(format!(
+                "{}:{} : une ligne doit contenir un caractère et son code",
+                alphabet,
+                number + 1
+            )
+            .into());
+        };
+        let mut characters = character.chars();
+        let (Some(character), None) = (characters.next(), characters.next()) else {
+            return Err(format!(
+                "{}:{} : {} n'est pas un caractère",
+                alphabet,
+                number + 1,
+                character
+            )
+            .into());
+        };
+        let code: String = Notation::Unicode.normalize(code);
+        if code.is_empty() || !code.chars().all(|symbol| symbol == '.' || symbol == '-') {
+            return Err(format!(
+                "{}:{} : code Morse invalide : {}",
+                alphabet,
+                number + 1,
+                code
+            )
+            .into());
+        }
+        morse.add_character(character, &code);
+    }
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use std::{fmt, str::FromStr};
+
+/// Vitesse utilisée par défaut pour jouer ou exporter un message, en mots par minute.
+pub(crate) const DEFAULT_WPM: f32 = 20.0;
+
+/// Fréquence utilisée par défaut pour jouer ou exporter un message, en Hz.
+pub(crate) const DEFAULT_TONE: f32 = 600.0;
+
+/// Sous-commande demandée.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Command {
+    Encode,
+    Decode,
+    Translate,
+    Play,
+    Wav,
+    Listen,
+    Repl,
+    Drill,
+    Help,
+}
+
+/// Notation des points et des tirets en entrée et en sortie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Notation {
+    /// Points et tirets ASCII (`.-`).
+    Ascii,
+    /// Point médian et signe moins Unicode (`·−`).
+    Unicode,
+}
+
+impl Notation {
+    /// Écrit un code Morse (en notation ASCII) dans cette notation.
+    pub(crate) fn format(self, morse_code: &str) -> String {
+        match self {
+            Notation::Ascii => morse_code.to_string(),
+            Notation::Unicode => morse_code
+                .chars()
+                .map(|symbol| match symbol {
+                    '.' => '·',
+                    '-' => '−',
+                    other => other,
+                })
+                .collect(),
+        }
+    }
+
+    /// Ramène un code Morse écrit dans cette notation à la notation ASCII.
+    pub(crate) fn normalize(self, morse_code: &str) -> String {
+        match self {
+            Notation::Ascii => morse_code.to_string(),
+            Notation::Unicode => morse_code
+                .chars()
+                .map(|symbol| match symbol {
+                    '·' | '•' | '∙' => '.',
+                    '−' | '–' | '—' => '-',
+                    other => other,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Options de la ligne de commande.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Options {
+    pub(crate) command: Command,
+    pub(crate) wpm: Option<f32>,
+    pub(crate) farnsworth: Option<f32>,
+    pub(crate) tone: Option<f32>,
+    pub(crate) dah_ratio: Option<f32>,
+    pub(crate) weight: Option<f32>,
+    pub(crate) alphabet: Option<String>,
+    pub(crate) notation: Notation,
+    pub(crate) output: Option<String>,
+    pub(crate) count: Option<usize>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) verbose: bool,
+    pub(crate) arguments: Vec<String>,
+}
+
+/// Erreur d'utilisation de la ligne de commande.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UsageError(pub(crate) String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Analyse les arguments (sans le nom du programme).
+pub(crate) fn parse<I: IntoIterator<Item = String>>(arguments: I) -> Result<Options, UsageError> {
+    let mut arguments = arguments.into_iter();
+    let command: Command = match arguments.next().as_deref() {
+        Some("encode") => Command::Encode,
+        Some("decode") => Command::Decode,
+        Some("translate") => Command::Translate,
+        Some("play") => Command::Play,
+        Some("wav") => Command::Wav,
+        Some("listen") => Command::Listen,
+        Some("repl") => Command::Repl,
+        Some("drill") => Command::Drill,
+        Some("help" | "-h" | "--help") | None => Command::Help,
+        Some(other) => return Err(UsageError(format!("sous-commande inconnue : {}", other))),
+    };
+    let mut options = Options {
+        command,
+        wpm: None,
+        farnsworth: None,
+        tone: None,
+        dah_ratio: None,
+        weight: None,
+        alphabet: None,
+        notation: Notation::Ascii,
+        output: None,
+        count: None,
+        seed: None,
+        verbose: false,
+        arguments: Vec::new(),
+    };
+
+    let mut only_arguments: bool = false;
+    while let Some(argument) = arguments.next() {
+        if only_arguments || !is_option(&argument) {
+            options.arguments.push(argument);
+            continue;
+        }
+        // Les options acceptent `--nom valeur` et `--nom=valeur`
+        let (name, inline_value) = match argument.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (argument, None),
+        };
+        let mut value = |name: &str| -> Result<String, UsageError> {
+            inline_value
+                .clone()
+                .or_else(|| arguments.next())
+                .ok_or_else(|| UsageError(format!("{} attend une valeur", name)))
+        };
+        match name.as_str() {
+            "--" => only_arguments = true,
+            "--wpm" => options.wpm = Some(positive(&name, &value(&name)?)?),
+            "--farnsworth" => options.farnsworth = Some(positive(&name, &value(&name)?)?),
+            "--tone" => options.tone = Some(positive(&name, &value(&name)?)?),
+            "--ratio" => options.dah_ratio = Some(positive(&name, &value(&name)?)?),
+            "--weight" => options.weight = Some(positive(&name, &value(&name)?)?),
+            "--alphabet" => options.alphabet = Some(value(&name)?),
+            "--notation" => {
+                options.notation = match value(&name)?.as_str() {
+                    "ascii" => Notation::Ascii,
+                    "unicode" => Notation::Unicode,
+                    other => {
+                        return Err(UsageError(format!(
+                            "notation inconnue : {} (ascii ou unicode)",
+                            other
+                        )))
+                    }
+                }
+            }
+            "-o" | "--output" => options.output = Some(value(&name)?),
+            "--count" => options.count = Some(integer(&name, &value(&name)?)?),
+            "--seed" => options.seed = Some(integer(&name, &value(&name)?)?),
+            "-v" | "--verbose" => options.verbose = true,
+            "--help" => options.command = Command::Help,
+            _ => return Err(UsageError(format!("option inconnue : {}", name))),
+        }
+    }
+    Ok(options)
+}
+
+/// Indique si un argument est une option plutôt qu'une entrée.
+///
+/// Un code Morse peut commencer par `--` (`--.` pour G, `---` pour O) : seuls `--`, `-o`, `-v`
+/// et les mots `--nom` (ou `--nom=valeur`) faits de lettres et de chiffres sont des options.
+fn is_option(argument: &str) -> bool {
+    if argument == "--" || argument == "-o" || argument == "-v" {
+        return true;
+    }
+    match argument.strip_prefix("--") {
+        Some(option) => {
+            let name: &str = option.split_once('=').map_or(option, |(name, _)| name);
+            !name.is_empty() && name.chars().all(|c| c.is_alphanumeric())
+        }
+        None => false,
+    }
+}
+
+/// Lit un nombre strictement positif.
+fn positive(name: &str, value: &str) -> Result<f32, UsageError> {
+    match value.parse::<f32>() {
+        Ok(number) if number.is_finite() && number > 0.0 => Ok(number),
+        _ => Err(UsageError(format!(
+            "{} attend un nombre positif, reçu : {}",
+            name, value
+        ))),
+    }
+}
+
+/// Lit un entier positif ou nul.
+fn integer<T: FromStr>(name: &str, value: &str) -> Result<T, UsageError> {
+    value.parse::<T>().map_err(|_| {
+        UsageError(format!(
+            "{} attend un entier positif, reçu : {}",
+            name, value
+        ))
+    })
+}
+
+/// Texte d'aide affiché par `morseus help`.
+pub(crate) const USAGE: &str = "\
+Utilisation : morseus <sous-commande> [options] [arguments...]
+
+Sous-commandes :
+  encode [TEXTE...]     encode du texte en code Morse
+  decode [MORSE...]     décode du code Morse en texte
+  translate [TEXTE...]  traduit chaque mot : le Morse est décodé, le texte encodé
+  play [TEXTE...]       joue un texte ou un code Morse sur le dispositif audio
+  wav [TEXTE...]        exporte un texte ou un code Morse en fichier WAV
+  listen <FICHIER.wav>  décode un enregistrement WAV
+  repl                  mode interactif (terminal Unix) : le texte tapé est envoyé en direct,
+                        et la barre d'espace sert de manipulateur (Tab) dont les signaux sont
+                        décodés, si le terminal suit le protocole clavier de kitty
+  drill icr [CARACTÈRES]
+                        reconnaissance instantanée (terminal Unix) : chaque caractère (A à Z
+                        par défaut) est joué seul, et le temps de réaction est mesuré
+  help                  affiche cette aide
+
+Sans argument, encode, decode et translate traitent l'entrée standard ligne par ligne ;
+play et wav lisent toute l'entrée standard.
+
+Options :
+  --wpm <N>             vitesse en mots par minute (20 par défaut pour play, wav, repl et
+                        drill)
+  --farnsworth <N>      vitesse effective Farnsworth, inférieure à --wpm
+  --ratio <N>           rapport tiret/point (3 par défaut)
+  --weight <N>          pondération en % : au-delà de 50, signaux allongés et silences
+                        raccourcis d'autant (50 par défaut, entre 10 et 90)
+  --tone <HZ>           fréquence des bips (600 Hz par défaut) ; avec listen, écoute cette
+                        fréquence au lieu de détecter la tonalité
+  --alphabet <NOM>      international (par défaut) ou fichier de lignes « CARACTÈRE CODE »
+                        qui ajoutent ou remplacent des caractères
+  --notation <NOM>      ascii (.-, par défaut) ou unicode (·−)
+  -o, --output <FICHIER>
+                        fichier créé par wav (morse.wav par défaut) ; avec drill,
+                        statistiques de la séance en JSON
+  --count <N>           avec drill, nombre de caractères joués (50 par défaut)
+  --seed <N>            avec drill, graine du tirage pour rejouer le même exercice
+  -v, --verbose         avec listen, affiche la vitesse et la tonalité sur la sortie d'erreur
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_words(words: &[&str]) -> Result<Options, UsageError> {
+        parse(words.iter().map(|word| word.to_string()))
+    }
+
+    #[test]
+    fn morse_starting_with_dashes_is_an_argument() {
+        for morse in [
+            "--. ---", "-- ---", "--", "---", "--..", "--.-", "-----", "--...", "---..",
+        ] {
+            let options: Options = parse_words(&["decode", "--", morse]).unwrap();
+            assert_eq!(options.arguments, vec![morse.to_string()]);
+        }
+        for morse in ["--. ---", "-- ---", "---", "--..", "--.- / --..", "----."] {
+            let options: Options = parse_words(&["decode", morse]).unwrap();
+            assert_eq!(options.arguments, vec![morse.to_string()]);
+        }
+    }
+
+    #[test]
+    fn options_are_parsed() {
+        let options: Options = parse_words(&[
+            "wav",
+            "--wpm",
+            "25",
+            "--weight=30",
+            "-o",
+            "w.wav",
+            "-v",
+            "SOS",
+        ])
+        .unwrap();
+        assert_eq!(options.command, Command::Wav);
+        assert_eq!(options.wpm, Some(25.0));
+        assert_eq!(options.weight, Some(30.0));
+        assert_eq!(options.output.as_deref(), Some("w.wav"));
+        assert!(options.verbose);
+        assert_eq!(options.arguments, vec!["SOS".to_string()]);
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        let options: Options = parse_words(&["encode", "--", "--wpm", "-v"]).unwrap();
+        assert_eq!(options.wpm, None);
+        assert!(!options.verbose);
+        assert_eq!(
+            options.arguments,
+            vec!["--wpm".to_string(), "-v".to_string()]
+        );
+    }
+
+    #[test]
+    fn unknown_option_is_an_error() {
+        assert_eq!(
+            parse_words(&["decode", "--speed", "20"]),
+            Err(UsageError("option inconnue : --speed".to_string()))
+        );
+        assert_eq!(
+            parse_words(&["wav", "--wpm"]),
+            Err(UsageError("--wpm attend une valeur".to_string()))
+        );
+        assert!(parse_words(&["wav", "--wpm", "0"]).is_err());
+    }
+
+    #[test]
+    fn subcommands() {
+        assert_eq!(parse_words(&[]).unwrap().command, Command::Help);
+        assert_eq!(parse_words(&["--help"]).unwrap().command, Command::Help);
+        assert_eq!(
+            parse_words(&["listen", "--help"]).unwrap().command,
+            Command::Help
+        );
+        assert!(parse_words(&["morse"]).is_err());
+    }
+}
@@ -0,0 +1,357 @@
+//! Mode interactif : le texte tapé est envoyé en Morse au fil de la frappe, et la barre
+//! d'espace peut servir de manipulateur droit dont les signaux sont décodés en direct.
+
+use std::{
+    collections::VecDeque,
+    error::Error,
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use morseus::{DecodedCharacter, Morse, RodioSink, Sidetone, StreamingTimingDecoder};
+
+use crate::{
+    options::Notation,
+    terminal::{self, Input, Key, KeyAction, RawTerminal},
+};
+
+/// Vitesses accessibles avec les flèches, en mots par minute.
+const WPM_RANGE: (f32, f32) = (5.0, 60.0);
+
+/// Intervalle de mise à jour du décodeur et de l'écran.
+const TICK: Duration = Duration::from_millis(20);
+
+/// Nombre maximal de caractères affichés sur chaque ligne.
+const WIDTH: usize = 78;
+
+const HELP: &str = "Tapez du texte pour l'envoyer. Tab : manipulateur (barre d'espace), \
+                    ↑/↓ : vitesse, Retour arrière : effacer, Échap : quitter.";
+
+/// Mode de la session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Le texte tapé est envoyé.
+    Send,
+    /// La barre d'espace est un manipulateur droit.
+    Key,
+}
+
+/// Événement reçu par la boucle principale.
+enum Event {
+    /// Octets lus sur le terminal.
+    Input(Vec<u8>, Instant),
+    /// Un caractère commence à être envoyé.
+    Sending(char, String),
+    /// Le dispositif audio n'a pas pu être ouvert.
+    AudioError(String),
+}
+
+/// État partagé avec le thread d'envoi.
+struct Outbox {
+    /// Caractères tapés qui n'ont pas encore été envoyés.
+    pending: Mutex<VecDeque<char>>,
+    /// Vitesse d'envoi (bits d'un `f32`).
+    wpm: AtomicU32,
+    finished: AtomicBool,
+}
+
+/// Session interactive.
+struct Session {
+    morse: Morse,
+    notation: Notation,
+    farnsworth: Option<f32>,
+    mode: Mode,
+    wpm: f32,
+    outbox: Arc<Outbox>,
+    /// Texte envoyé ou reçu.
+    transcript: String,
+    /// Dernier caractère envoyé ou décodé, avec son code.
+    last: Option<(char, String)>,
+    /// `true` lorsque le terminal signale le relâchement des touches.
+    key_release: bool,
+    decoder: StreamingTimingDecoder,
+    sidetone: Option<Sidetone>,
+    start: Instant,
+    key_down: bool,
+    /// Message affiché sous la ligne d'état jusqu'à la prochaine action.
+    notice: Option<String>,
+}
+
+/// Lance la session interactive.
+pub(crate) fn run(
+    morse: Morse,
+    wpm: f32,
+    farnsworth: Option<f32>,
+    notation: Notation,
+) -> Result<(), Box<dyn Error>> {
+    let terminal: RawTerminal = RawTerminal::enable()?;
+    let (sender, receiver) = channel();
+    terminal::spawn_reader(sender.clone(), Event::Input);
+
+    let outbox: Arc<Outbox> = Arc::new(Outbox {
+        pending: Mutex::new(VecDeque::new()),
+        wpm: AtomicU32::new(wpm.to_bits()),
+        finished: AtomicBool::new(false),
+    });
+    spawn_sender(morse.clone(), farnsworth, Arc::clone(&outbox), sender);
+
+    let mut session = Session {
+        decoder: morse.timing_decoder(),
+        morse,
+        notation,
+        farnsworth,
+        mode: Mode::Send,
+        wpm,
+        outbox,
+        transcript: String::new(),
+        last: None,
+        key_release: false,
+        sidetone: None,
+        start: Instant::now(),
+        key_down: false,
+        notice: None,
+    };
+    session.set_wpm(wpm);
+    let result = session.run(&receiver);
+    session.outbox.finished.store(true, Ordering::SeqCst);
+    drop(terminal);
+    result
+}
+
+/// Démarre le thread qui envoie les caractères tapés, un à un, sur le dispositif audio.
+fn spawn_sender(
+    mut morse: Morse,
+    farnsworth: Option<f32>,
+    outbox: Arc<Outbox>,
+    events: Sender<Event>,
+) {
+    thread::spawn(move || {
+        // Le flux audio doit être ouvert sur le thread qui l'utilise
+        let mut sink: RodioSink = match RodioSink::try_default() {
+            Ok(sink) => sink,
+            Err(error) => {
+                let _ = events.send(Event::AudioError(error.to_string()));
+                return;
+            }
+        };
+        while !outbox.finished.load(Ordering::SeqCst) {
+            let next: Option<char> = outbox.pending.lock().unwrap().pop_front();
+            let Some(character) = next else {
+                thread::sleep(TICK);
+                continue;
+            };
+            let Some(code) = morse.get_morse_code(character).cloned() else {
+                continue;
+            };
+            set_timing(
+                &mut morse,
+                f32::from_bits(outbox.wpm.load(Ordering::SeqCst)),
+                farnsworth,
+            );
+            if events
+                .send(Event::Sending(character, code.clone()))
+                .is_err()
+            {
+                return;
+            }
+            // L'espace qui suit le code sépare ce caractère du suivant
+            let _ = morse.play_morse_code_on(&format!("{} ", code), &mut sink);
+        }
+    });
+}
+
+/// Règle la vitesse d'envoi, avec un espacement Farnsworth éventuel.
+fn set_timing(morse: &mut Morse, wpm: f32, farnsworth: Option<f32>) {
+    match farnsworth {
+        Some(effective_wpm) => morse.set_farnsworth(wpm, effective_wpm),
+        None => morse.set_wpm(wpm),
+    }
+}
+
+impl Session {
+    /// Traite les événements jusqu'à ce que l'utilisateur quitte la session.
+    fn run(&mut self, receiver: &Receiver<Event>) -> Result<(), Box<dyn Error>> {
+        let mut output = io::stdout();
+        write!(output, "{}\r\n\r\n\r\n", HELP)?;
+        self.draw(&mut output)?;
+        loop {
+            match receiver.recv_timeout(TICK) {
+                Ok(Event::Input(bytes, at)) => {
+                    for input in terminal::parse(&bytes) {
+                        if !self.handle(input, at) {
+                            return Ok(());
+                        }
+                    }
+                }
+                Ok(Event::Sending(character, code)) => {
+                    self.transcript.push(character);
+                    self.last = Some((character, code));
+                }
+                Ok(Event::AudioError(error)) => {
+                    return Err(format!("dispositif audio : {}", error).into())
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+            if self.mode == Mode::Key {
+                let decoded: Vec<DecodedCharacter> = self.decoder.tick(self.start.elapsed());
+                self.receive(decoded);
+            }
+            self.draw(&mut output)?;
+        }
+    }
+
+    /// Traite un événement du terminal. Retourne `false` pour quitter.
+    fn handle(&mut self, input: Input, at: Instant) -> bool {
+        let (key, action) = match input {
+            Input::Key(key, action) => (key, action),
+            Input::KeyboardProtocol => {
+                self.key_release = true;
+                return true;
+            }
+            Input::DeviceAttributes => return true,
+        };
+        if self.mode == Mode::Key && key == Key::Space {
+            self.key(action != KeyAction::Release, at);
+            return true;
+        }
+        if action != KeyAction::Press {
+            return true;
+        }
+        self.notice = None;
+        match key {
+            Key::Escape | Key::Interrupt => return false,
+            Key::Up => self.set_wpm(self.wpm + 1.0),
+            Key::Down => self.set_wpm(self.wpm - 1.0),
+            Key::Tab => self.toggle_mode(),
+            Key::Backspace if self.mode == Mode::Send => {
+                self.outbox.pending.lock().unwrap().pop_back();
+            }
+            Key::Space | Key::Enter if self.mode == Mode::Send => self.type_character(' '),
+            Key::Character(character) if self.mode == Mode::Send => {
+                for character in self.notation.normalize(&character.to_string()).chars() {
+                    for character in character.to_uppercase() {
+                        self.type_character(character);
+                    }
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Ajoute un caractère au tampon d'envoi s'il fait partie de l'alphabet.
+    fn type_character(&mut self, character: char) {
+        if self.morse.get_morse_code(character).is_some() {
+            self.outbox.pending.lock().unwrap().push_back(character);
+        } else {
+            self.notice = Some(format!("« {} » n'a pas de code Morse", character));
+        }
+    }
+
+    /// Enfonce ou relâche le manipulateur.
+    fn key(&mut self, down: bool, at: Instant) {
+        if down == self.key_down {
+            return;
+        }
+        self.key_down = down;
+        if let Some(sidetone) = &self.sidetone {
+            if down {
+                sidetone.key_down();
+            } else {
+                sidetone.key_up();
+            }
+        }
+        let decoded: Vec<DecodedCharacter> = self
+            .decoder
+            .key_event(down, at.saturating_duration_since(self.start));
+        self.receive(decoded);
+    }
+
+    /// Ajoute des caractères décodés au texte reçu.
+    fn receive(&mut self, decoded: Vec<DecodedCharacter>) {
+        for character in decoded {
+            let received: char = character.character.unwrap_or('?');
+            self.transcript.push(received);
+            if received != ' ' {
+                self.last = Some((received, character.code));
+            }
+        }
+    }
+
+    /// Passe du mode envoi au mode manipulateur, et inversement.
+    fn toggle_mode(&mut self) {
+        match self.mode {
+            Mode::Send => {
+                if !self.key_release {
+                    self.notice = Some(
+                        "ce terminal ne signale pas le relâchement des touches : le \
+                         manipulateur nécessite le protocole clavier de kitty"
+                            .to_string(),
+                    );
+                    return;
+                }
+                if self.sidetone.is_none() {
+                    match self.morse.sidetone() {
+                        Ok(sidetone) => self.sidetone = Some(sidetone),
+                        Err(error) => self.notice = Some(format!("tonalité : {}", error)),
+                    }
+                }
+                self.mode = Mode::Key;
+                self.decoder = self.morse.timing_decoder();
+                self.start = Instant::now();
+                self.transcript.push_str(" | ");
+            }
+            Mode::Key => {
+                self.key(false, Instant::now());
+                let decoded: Vec<DecodedCharacter> = self.decoder.finish();
+                self.receive(decoded);
+                self.mode = Mode::Send;
+                self.transcript.push_str(" | ");
+            }
+        }
+    }
+
+    /// Change la vitesse d'envoi et la vitesse attendue du manipulateur.
+    fn set_wpm(&mut self, wpm: f32) {
+        self.wpm = wpm.clamp(WPM_RANGE.0, WPM_RANGE.1);
+        self.outbox.wpm.store(self.wpm.to_bits(), Ordering::SeqCst);
+        set_timing(&mut self.morse, self.wpm, self.farnsworth);
+    }
+
+    /// Redessine le texte envoyé ou reçu et la ligne d'état, sous l'aide.
+    fn draw<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let mut status: String = match self.mode {
+            Mode::Send => format!("[envoi {:.0} wpm]", self.wpm),
+            Mode::Key => format!("[manipulateur, reçu {:.0} wpm]", self.decoder.wpm()),
+        };
+        if let Some((character, code)) = &self.last {
+            status.push_str(&format!(" {} {}", character, self.notation.format(code)));
+        }
+        let pending: String = self.outbox.pending.lock().unwrap().iter().collect();
+        if !pending.is_empty() {
+            status.push_str(&format!(" | en attente : {}", pending));
+        }
+        let notice: &str = self.notice.as_deref().unwrap_or("");
+        write!(
+            output,
+            "\x1b[3A\r\x1b[K{}\r\n\x1b[K{}\r\n\x1b[K{}\r\n",
+            tail(&self.transcript),
+            status.chars().take(WIDTH).collect::<String>(),
+            notice.chars().take(WIDTH).collect::<String>(),
+        )?;
+        output.flush()
+    }
+}
+
+/// Retourne la fin d'un texte, limitée à la largeur affichée.
+fn tail(text: &str) -> String {
+    let length: usize = text.chars().count();
+    text.chars().skip(length.saturating_sub(WIDTH)).collect()
+}
@@ -0,0 +1,219 @@
+//! Accès au terminal sans dépendance : mode brut via `stty` et lecture des touches, y compris
+//! leur relâchement lorsque le terminal prend en charge le protocole clavier de kitty.
+
+use std::{
+    io::{self, Read, Write},
+    process::{Command, Stdio},
+    sync::mpsc::Sender,
+    thread,
+    time::Instant,
+};
+
+/// Active le protocole clavier de kitty : touches sans ambiguïté (1), types d'événements (2),
+/// touches avec Maj (4) et toutes les touches en séquences d'échappement (8).
+const ENABLE_KEYBOARD_PROTOCOL: &str = "\x1b[>15u";
+/// Restaure le protocole clavier précédent.
+const DISABLE_KEYBOARD_PROTOCOL: &str = "\x1b[<u";
+/// Demande les options du protocole clavier, puis les attributs du terminal : un terminal qui
+/// ne connaît pas le protocole ne répond qu'à la seconde demande.
+const QUERY_KEYBOARD_PROTOCOL: &str = "\x1b[?u\x1b[c";
+
+/// Terminal en mode brut : les touches sont reçues une à une, sans écho. Le mode d'origine est
+/// restauré à la destruction.
+pub(crate) struct RawTerminal {
+    saved: String,
+}
+
+impl RawTerminal {
+    /// Passe le terminal en mode brut et demande le protocole clavier de kitty.
+    pub(crate) fn enable() -> io::Result<Self> {
+        let saved: String = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        let mut output = io::stdout();
+        write!(
+            output,
+            "{}{}",
+            ENABLE_KEYBOARD_PROTOCOL, QUERY_KEYBOARD_PROTOCOL
+        )?;
+        output.flush()?;
+        Ok(RawTerminal {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let mut output = io::stdout();
+        let _ = write!(output, "{}\r\n", DISABLE_KEYBOARD_PROTOCOL);
+        let _ = output.flush();
+        let _ = stty(&[self.saved.as_str()]);
+    }
+}
+
+/// Exécute `stty` sur le terminal de l'entrée standard et retourne sa sortie.
+fn stty(arguments: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(arguments)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "l'entrée standard n'est pas un terminal",
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Lit l'entrée standard sur un thread dédié et envoie chaque lecture, avec son instant de
+/// réception, convertie par `wrap`.
+pub(crate) fn spawn_reader<T, F>(sender: Sender<T>, wrap: F)
+where
+    T: Send + 'static,
+    F: Fn(Vec<u8>, Instant) -> T + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut input = io::stdin();
+        let mut buffer = [0u8; 256];
+        loop {
+            match input.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(length) => {
+                    if sender
+                        .send(wrap(buffer[..length].to_vec(), Instant::now()))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Touche du clavier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Key {
+    Character(char),
+    Space,
+    Enter,
+    Tab,
+    Backspace,
+    Escape,
+    Up,
+    Down,
+    /// Ctrl+C ou Ctrl+D.
+    Interrupt,
+}
+
+/// Type d'événement clavier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyAction {
+    Press,
+    Repeat,
+    Release,
+}
+
+/// Événement reçu du terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Input {
+    Key(Key, KeyAction),
+    /// Le terminal a répondu à la demande du protocole clavier de kitty : le relâchement des
+    /// touches sera signalé.
+    KeyboardProtocol,
+    /// Le terminal a répondu à la demande de ses attributs.
+    DeviceAttributes,
+}
+
+/// Décode les octets lus sur le terminal en événements.
+pub(crate) fn parse(bytes: &[u8]) -> Vec<Input> {
+    let mut inputs: Vec<Input> = Vec::new();
+    let text = String::from_utf8_lossy(bytes);
+    let mut characters = text.chars().peekable();
+    while let Some(character) = characters.next() {
+        let key: Key = match character {
+            '\x1b' if characters.peek() == Some(&'[') => {
+                characters.next();
+                let mut parameters: String = String::new();
+                let mut last: Option<char> = None;
+                for character in characters.by_ref() {
+                    if ('\x40'..='\x7e').contains(&character) {
+                        last = Some(character);
+                        break;
+                    }
+                    parameters.push(character);
+                }
+                if let Some(input) = last.and_then(|last| escape_sequence(&parameters, last)) {
+                    inputs.push(input);
+                }
+                continue;
+            }
+            '\x1b' => Key::Escape,
+            '\x03' | '\x04' => Key::Interrupt,
+            '\x7f' | '\x08' => Key::Backspace,
+            '\r' | '\n' => Key::Enter,
+            '\t' => Key::Tab,
+            ' ' => Key::Space,
+            character if character.is_control() => continue,
+            character => Key::Character(character),
+        };
+        inputs.push(Input::Key(key, KeyAction::Press));
+    }
+    inputs
+}
+
+/// Décode une séquence `CSI paramètres final`.
+fn escape_sequence(parameters: &str, last: char) -> Option<Input> {
+    if let Some(parameters) = parameters.strip_prefix('?') {
+        return match last {
+            'u' => Some(Input::KeyboardProtocol),
+            'c' if !parameters.is_empty() => Some(Input::DeviceAttributes),
+            _ => None,
+        };
+    }
+    // Protocole de kitty : `code[:code avec Maj[:code de base]];modificateurs[:événement]`
+    let mut fields = parameters.split(';');
+    let mut codes = fields.next().unwrap_or("").split(':');
+    let code: u32 = codes.next().and_then(|code| code.parse().ok()).unwrap_or(1);
+    let shifted: Option<u32> = codes.next().and_then(|code| code.parse().ok());
+    let (modifiers, event): (u32, Option<&str>) = match fields.next() {
+        Some(field) => {
+            let mut parts = field.split(':');
+            let modifiers: u32 = parts
+                .next()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(1);
+            (modifiers.saturating_sub(1), parts.next())
+        }
+        None => (0, None),
+    };
+    let action: KeyAction = match event {
+        Some("2") => KeyAction::Repeat,
+        Some("3") => KeyAction::Release,
+        _ => KeyAction::Press,
+    };
+    let control: bool = modifiers & 4 != 0;
+    let code: u32 = match shifted {
+        Some(shifted) if modifiers & 1 != 0 => shifted,
+        _ => code,
+    };
+    let key: Key = match (last, code) {
+        ('A', _) => Key::Up,
+        ('B', _) => Key::Down,
+        ('u', 99 | 100) if control => Key::Interrupt,
+        ('u', 27) => Key::Escape,
+        ('u', 13) => Key::Enter,
+        ('u', 9) => Key::Tab,
+        ('u', 127 | 8) => Key::Backspace,
+        ('u', 32) => Key::Space,
+        ('u', _) if control => return None,
+        ('u', code) => match char::from_u32(code) {
+            Some(character) if !character.is_control() => Key::Character(character),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(Input::Key(key, action))
+}
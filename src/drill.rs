@@ -0,0 +1,447 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{json::Value, morse::Morse, rng::Rng};
+
+/// Exercice de reconnaissance instantanée des caractères (ICR).
+///
+/// Chaque question joue un seul caractère ; l'élève doit taper le caractère reconnu le plus
+/// vite possible. Le temps de réaction est mesuré depuis la fin du caractère joué : une
+/// réponse donnée pendant le caractère compte pour un temps nul, et une réponse qui arrive
+/// après le délai maximal est comptée comme manquée. Les instants sont des durées depuis un
+/// même point de départ, choisi par l'appelant.
+#[derive(Debug, Clone)]
+pub struct IcrDrill {
+    characters: Vec<char>,
+    length: usize,
+    timeout: Duration,
+    rng: Rng,
+    prompt: Option<IcrPrompt>,
+    trials: Vec<IcrTrial>,
+}
+
+/// Question posée par `IcrDrill::next_prompt` : le caractère à jouer et son horaire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcrPrompt {
+    /// Caractère à jouer.
+    pub character: char,
+    /// Code Morse du caractère.
+    pub code: String,
+    /// Instant où le caractère commence à être joué.
+    pub started: Duration,
+    /// Instant où le dernier signal du caractère se termine.
+    pub ended: Duration,
+}
+
+/// Résultat d'une question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IcrTrial {
+    /// Caractère joué.
+    pub character: char,
+    /// Caractère tapé par l'élève, `None` s'il n'a pas répondu à temps.
+    pub answer: Option<char>,
+    /// Temps de réaction depuis la fin du caractère, `None` sans réponse à temps.
+    pub reaction_time: Option<Duration>,
+}
+
+impl IcrTrial {
+    /// Indique si l'élève a tapé le bon caractère à temps.
+    pub fn is_correct(&self) -> bool {
+        self.answer == Some(self.character)
+    }
+}
+
+/// Statistiques d'un caractère au cours d'une séance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IcrCharacterStatistics {
+    /// Nombre de fois où le caractère a été joué.
+    pub attempts: usize,
+    /// Nombre de bonnes réponses.
+    pub correct: usize,
+    /// Temps de réaction moyen des bonnes réponses.
+    pub mean_reaction_time: Option<Duration>,
+}
+
+impl IcrCharacterStatistics {
+    /// Retourne la part des bonnes réponses, entre 0.0 et 1.0.
+    pub fn accuracy(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.correct as f32 / self.attempts as f32
+        }
+    }
+}
+
+/// Statistiques d'une séance, calculées par `IcrDrill::statistics`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcrStatistics {
+    /// Nombre de questions terminées.
+    pub attempts: usize,
+    /// Nombre de bonnes réponses.
+    pub correct: usize,
+    /// Temps de réaction moyen des bonnes réponses.
+    pub mean_reaction_time: Option<Duration>,
+    /// Statistiques de chaque caractère joué.
+    pub characters: BTreeMap<char, IcrCharacterStatistics>,
+}
+
+impl IcrStatistics {
+    /// Retourne la part des bonnes réponses, entre 0.0 et 1.0.
+    pub fn accuracy(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.correct as f32 / self.attempts as f32
+        }
+    }
+
+    /// Retourne au plus `count` caractères parmi les plus lents à reconnaître, du plus lent au
+    /// moins lent. Les caractères jamais reconnus passent en premier.
+    pub fn slowest(&self, count: usize) -> Vec<char> {
+        let mut characters: Vec<(char, Option<Duration>)> = self
+            .characters
+            .iter()
+            .map(|(character, statistics)| (*character, statistics.mean_reaction_time))
+            .collect();
+        characters.sort_by_key(|(_, time)| std::cmp::Reverse(time.unwrap_or(Duration::MAX)));
+        characters
+            .into_iter()
+            .take(count)
+            .map(|(character, _)| character)
+            .collect()
+    }
+}
+
+impl IcrDrill {
+    /// Crée un exercice de 50 questions sur les caractères donnés, avec un délai de réponse
+    /// de 3 secondes.
+    ///
+    /// # Arguments
+    ///
+    /// * `characters` - Les caractères à reconnaître ; ceux que l'alphabet ne connaît pas
+    ///   sont ignorés au moment de poser les questions.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{IcrDrill, IcrPrompt, IcrTrial, Morse};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_wpm(20.0);
+    ///     let mut drill = IcrDrill::new(&['K', 'M']);
+    ///     drill.set_length(2);
+    ///     drill.set_seed(1);
+    ///
+    ///     // Le caractère est joué à partir de 1 s ; l'élève répond 300 ms après sa fin
+    ///     let prompt: IcrPrompt = drill.next_prompt(&morse, Duration::from_secs(1)).unwrap();
+    ///     assert!(prompt.ended > prompt.started);
+    ///     let trial: IcrTrial =
+    ///         drill.answer(prompt.character, prompt.ended + Duration::from_millis(300)).unwrap();
+    ///     assert!(trial.is_correct());
+    ///     assert_eq!(trial.reaction_time, Some(Duration::from_millis(300)));
+    ///
+    ///     // Sans réponse, la question suivante compte la précédente comme manquée
+    ///     let prompt = drill.next_prompt(&morse, Duration::from_secs(3)).unwrap();
+    ///     assert!(drill.next_prompt(&morse, Duration::from_secs(10)).is_none());
+    ///     assert_eq!(drill.trials()[1].character, prompt.character);
+    ///     assert_eq!(drill.trials()[1].answer, None);
+    ///     assert!(drill.is_finished());
+    ///
+    ///     let statistics = drill.statistics();
+    ///     assert_eq!(statistics.attempts, 2);
+    ///     assert_eq!(statistics.accuracy(), 0.5);
+    ///     assert_eq!(statistics.mean_reaction_time, Some(Duration::from_millis(300)));
+    /// }
+    /// ```
+    pub fn new(characters: &[char]) -> Self {
+        let seed: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or(0);
+        IcrDrill {
+            characters: characters.to_vec(),
+            length: 50,
+            timeout: Duration::from_secs(3),
+            rng: Rng::new(seed),
+            prompt: None,
+            trials: Vec::new(),
+        }
+    }
+
+    /// Change le nombre de questions de l'exercice.
+    pub fn set_length(&mut self, length: usize) {
+        self.length = length;
+    }
+
+    /// Change le délai au-delà duquel une réponse est comptée comme manquée.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Retourne le délai au-delà duquel une réponse est comptée comme manquée.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Change la graine du tirage, pour un exercice reproductible.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    /// Retourne la question en attente de réponse.
+    pub fn prompt(&self) -> Option<&IcrPrompt> {
+        self.prompt.as_ref()
+    }
+
+    /// Retourne les résultats des questions terminées, dans l'ordre.
+    pub fn trials(&self) -> &[IcrTrial] {
+        &self.trials
+    }
+
+    /// Indique si toutes les questions ont été posées et terminées.
+    pub fn is_finished(&self) -> bool {
+        self.prompt.is_none() && self.trials.len() >= self.length
+    }
+
+    /// Tire le prochain caractère et calcule quand il finira d'être joué. Une question encore
+    /// en attente est d'abord comptée comme manquée. Le même caractère n'est jamais tiré deux
+    /// fois de suite, pour que chaque question soit une vraie reconnaissance.
+    ///
+    /// Retourne `None` lorsque l'exercice est terminé ou qu'aucun caractère n'est connu de
+    /// l'alphabet.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse` - L'alphabet et la vitesse utilisés pour jouer le caractère.
+    /// * `at` - L'instant où le caractère commence à être joué.
+    pub fn next_prompt(&mut self, morse: &Morse, at: Duration) -> Option<IcrPrompt> {
+        if let Some(prompt) = self.prompt.take() {
+            self.trials.push(IcrTrial {
+                character: prompt.character,
+                answer: None,
+                reaction_time: None,
+            });
+        }
+        if self.trials.len() >= self.length {
+            return None;
+        }
+        let characters: Vec<char> = self
+            .characters
+            .iter()
+            .copied()
+            .filter(|character| morse.get_morse_code(*character).is_some())
+            .collect();
+        let previous: Option<char> = self.trials.last().map(|trial| trial.character);
+        let choices: Vec<char> = match characters.len() {
+            0 => return None,
+            1 => characters,
+            _ => characters
+                .into_iter()
+                .filter(|character| Some(*character) != previous)
+                .collect(),
+        };
+        let character: char = choices[(self.rng.next_u64() % choices.len() as u64) as usize];
+        let code: String = morse.get_morse_code(character)?.clone();
+        // Le caractère se termine avec son dernier signal, sans la pause qui le suit
+        let length: Duration = morse.duration_of(&character.to_string()).duration;
+        let prompt = IcrPrompt {
+            character,
+            code,
+            started: at,
+            ended: at + length,
+        };
+        self.prompt = Some(prompt.clone());
+        Some(prompt)
+    }
+
+    /// Change l'instant où la question en attente commence à être jouée, en conservant la
+    /// durée du caractère. À appeler lorsque le premier échantillon est réellement confié au
+    /// dispositif audio, pour que la latence de son ouverture ou de sa file d'attente ne soit
+    /// pas comptée dans le temps de réaction.
+    ///
+    /// Retourne la question mise à jour, ou `None` si aucune question n'est en attente.
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - L'instant où le caractère commence réellement à être joué.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{IcrDrill, Morse};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let mut drill = IcrDrill::new(&['E']);
+    ///     let prompt = drill.next_prompt(&morse, Duration::from_secs(1)).unwrap();
+    ///     // Le dispositif audio n'a reçu le caractère que 150 ms plus tard
+    ///     let delayed = drill.set_prompt_start(Duration::from_millis(1150)).unwrap();
+    ///     assert_eq!(delayed.ended, prompt.ended + Duration::from_millis(150));
+    ///     let trial = drill.answer('E', delayed.ended + Duration::from_millis(200)).unwrap();
+    ///     assert_eq!(trial.reaction_time, Some(Duration::from_millis(200)));
+    /// }
+    /// ```
+    pub fn set_prompt_start(&mut self, at: Duration) -> Option<IcrPrompt> {
+        let prompt: &mut IcrPrompt = self.prompt.as_mut()?;
+        let length: Duration = prompt.ended - prompt.started;
+        prompt.started = at;
+        prompt.ended = at + length;
+        Some(prompt.clone())
+    }
+
+    /// Enregistre la réponse de l'élève à la question en attente et retourne son résultat.
+    ///
+    /// Retourne `None` si aucune question n'est en attente.
+    ///
+    /// # Arguments
+    ///
+    /// * `answer` - Le caractère tapé ; la casse est ignorée.
+    /// * `at` - L'instant de la frappe.
+    pub fn answer(&mut self, answer: char, at: Duration) -> Option<IcrTrial> {
+        let prompt: IcrPrompt = self.prompt.take()?;
+        let reaction_time: Duration = at.saturating_sub(prompt.ended);
+        let in_time: bool = reaction_time <= self.timeout;
+        let trial = IcrTrial {
+            character: prompt.character,
+            answer: in_time.then(|| answer.to_uppercase().next().unwrap_or(answer)),
+            reaction_time: in_time.then_some(reaction_time),
+        };
+        self.trials.push(trial);
+        Some(trial)
+    }
+
+    /// Calcule les statistiques des questions terminées.
+    pub fn statistics(&self) -> IcrStatistics {
+        let mut characters: BTreeMap<char, IcrCharacterStatistics> = BTreeMap::new();
+        let mut reaction_times: BTreeMap<char, Vec<Duration>> = BTreeMap::new();
+        for trial in &self.trials {
+            let statistics: &mut IcrCharacterStatistics =
+                characters.entry(trial.character).or_default();
+            statistics.attempts += 1;
+            if trial.is_correct() {
+                statistics.correct += 1;
+                reaction_times
+                    .entry(trial.character)
+                    .or_default()
+                    .extend(trial.reaction_time);
+            }
+        }
+        for (character, times) in &reaction_times {
+            characters
+                .get_mut(character)
+                .expect("caractère joué")
+                .mean_reaction_time = mean(times);
+        }
+        let times: Vec<Duration> = reaction_times.into_values().flatten().collect();
+        IcrStatistics {
+            attempts: self.trials.len(),
+            correct: characters
+                .values()
+                .map(|statistics| statistics.correct)
+                .sum(),
+            mean_reaction_time: mean(&times),
+            characters,
+        }
+    }
+
+    /// Retourne les statistiques de la séance et le détail des questions en JSON. Les temps
+    /// sont en millisecondes.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{IcrDrill, Morse};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut drill = IcrDrill::new(&['E']);
+    ///     drill.set_length(1);
+    ///     let prompt = drill.next_prompt(&Morse::new(), Duration::ZERO).unwrap();
+    ///     drill.answer('e', prompt.ended + Duration::from_millis(250));
+    ///     let json: String = drill.to_json();
+    ///     assert!(json.contains("\"accuracy\":1"));
+    ///     assert!(json.contains("\"mean_reaction_time\":250"));
+    ///     drill.save("icr.json").unwrap();
+    ///     std::fs::remove_file("icr.json").unwrap();
+    /// }
+    /// ```
+    pub fn to_json(&self) -> String {
+        let number = |value: f64| Value::Number(value);
+        let float = |value: f32| Value::Number(value.to_string().parse().unwrap_or(0.0));
+        let milliseconds = |time: Option<Duration>| match time {
+            Some(time) => Value::Number(time.as_millis() as f64),
+            None => Value::Null,
+        };
+        let statistics: IcrStatistics = self.statistics();
+        let characters: Vec<(String, Value)> = statistics
+            .characters
+            .iter()
+            .map(|(character, statistics)| {
+                (
+                    character.to_string(),
+                    Value::Object(vec![
+                        ("attempts".to_string(), number(statistics.attempts as f64)),
+                        ("correct".to_string(), number(statistics.correct as f64)),
+                        ("accuracy".to_string(), float(statistics.accuracy())),
+                        (
+                            "mean_reaction_time".to_string(),
+                            milliseconds(statistics.mean_reaction_time),
+                        ),
+                    ]),
+                )
+            })
+            .collect();
+        let trials: Vec<Value> = self
+            .trials
+            .iter()
+            .map(|trial| {
+                Value::Object(vec![
+                    (
+                        "character".to_string(),
+                        Value::String(trial.character.to_string()),
+                    ),
+                    (
+                        "answer".to_string(),
+                        trial
+                            .answer
+                            .map_or(Value::Null, |answer| Value::String(answer.to_string())),
+                    ),
+                    (
+                        "reaction_time".to_string(),
+                        milliseconds(trial.reaction_time),
+                    ),
+                ])
+            })
+            .collect();
+        Value::Object(vec![
+            ("attempts".to_string(), number(statistics.attempts as f64)),
+            ("correct".to_string(), number(statistics.correct as f64)),
+            ("accuracy".to_string(), float(statistics.accuracy())),
+            (
+                "mean_reaction_time".to_string(),
+                milliseconds(statistics.mean_reaction_time),
+            ),
+            ("characters".to_string(), Value::Object(characters)),
+            ("trials".to_string(), Value::Array(trials)),
+        ])
+        .to_string()
+    }
+
+    /// Enregistre les statistiques de la séance dans un fichier JSON (voir `to_json`).
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_json())
+    }
+}
+
+/// Retourne la moyenne de durées, `None` sans durée.
+fn mean(times: &[Duration]) -> Option<Duration> {
+    (!times.is_empty()).then(|| times.iter().sum::<Duration>() / times.len() as u32)
+}
@@ -0,0 +1,253 @@
+use std::{fmt, iter::Peekable, str::Chars};
+
+/// Valeur JSON minimale, utilisée pour enregistrer la progression d'un élève sans dépendre
+/// d'une bibliothèque externe.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    /// Les clés gardent leur ordre d'écriture.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Retourne la valeur associée à une clé d'un objet.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries
+                .iter()
+                .find_map(|(name, value)| (name == key).then_some(value)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Analyse un texte JSON.
+    pub(crate) fn parse(text: &str) -> Result<Value, String> {
+        let mut parser = Parser {
+            characters: text.chars().peekable(),
+            position: 0,
+        };
+        let value: Value = parser.value()?;
+        parser.skip_whitespace();
+        match parser.characters.peek() {
+            None => Ok(value),
+            Some(_) => Err(parser.error("fin du texte attendue")),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Number(number) if number.is_finite() => write!(f, "{}", number),
+            Value::Number(_) => f.write_str("null"),
+            Value::String(text) => {
+                f.write_str("\"")?;
+                for character in text.chars() {
+                    match character {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        character if character.is_control() => {
+                            write!(f, "\\u{:04x}", character as u32)?
+                        }
+                        character => write!(f, "{}", character)?,
+                    }
+                }
+                f.write_str("\"")
+            }
+            Value::Array(values) => {
+                f.write_str("[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(entries) => {
+                f.write_str("{")?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}:{}", Value::String(key.clone()), value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Analyseur récursif de JSON.
+struct Parser<'a> {
+    characters: Peekable<Chars<'a>>,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> Option<char> {
+        self.position += 1;
+        self.characters.next()
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("JSON invalide (caractère {}) : {}", self.position, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.characters.peek().is_some_and(|c| c.is_whitespace()) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(character) if character == expected => Ok(()),
+            _ => Err(self.error(&format!("« {} » attendu", expected))),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.characters.peek().copied() {
+            Some('{') => {
+                self.next();
+                let mut entries: Vec<(String, Value)> = Vec::new();
+                self.skip_whitespace();
+                if self.characters.peek() == Some(&'}') {
+                    self.next();
+                    return Ok(Value::Object(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key: String = self.string()?;
+                    self.expect(':')?;
+                    entries.push((key, self.value()?));
+                    self.skip_whitespace();
+                    match self.next() {
+                        Some(',') => continue,
+                        Some('}') => return Ok(Value::Object(entries)),
+                        _ => return Err(self.error("« , » ou « } » attendu")),
+                    }
+                }
+            }
+            Some('[') => {
+                self.next();
+                let mut values: Vec<Value> = Vec::new();
+                self.skip_whitespace();
+                if self.characters.peek() == Some(&']') {
+                    self.next();
+                    return Ok(Value::Array(values));
+                }
+                loop {
+                    values.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.next() {
+                        Some(',') => continue,
+                        Some(']') => return Ok(Value::Array(values)),
+                        _ => return Err(self.error("« , » ou « ] » attendu")),
+                    }
+                }
+            }
+            Some('"') => Ok(Value::String(self.string()?)),
+            Some('t') => self.keyword("true", Value::Bool(true)),
+            Some('f') => self.keyword("false", Value::Bool(false)),
+            Some('n') => self.keyword("null", Value::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let mut number: String = String::new();
+                while let Some(&c) = self.characters.peek() {
+                    if !(c.is_ascii_digit() || "+-.eE".contains(c)) {
+                        break;
+                    }
+                    number.push(c);
+                    self.next();
+                }
+                number
+                    .parse()
+                    .map(Value::Number)
+                    .map_err(|_| self.error("nombre invalide"))
+            }
+            _ => Err(self.error("valeur attendue")),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, String> {
+        for expected in keyword.chars() {
+            if self.next() != Some(expected) {
+                return Err(self.error(&format!("« {} » attendu", keyword)));
+            }
+        }
+        Ok(value)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        if self.next() != Some('"') {
+            return Err(self.error("chaîne attendue"));
+        }
+        let mut text: String = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => {
+                    let escaped: char = match self.next() {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let code: String = (0..4).filter_map(|_| self.next()).collect();
+                            u32::from_str_radix(&code, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("séquence \\u invalide"))?
+                        }
+                        Some(character @ ('"' | '\\' | '/')) => character,
+                        _ => return Err(self.error("échappement invalide")),
+                    };
+                    text.push(escaped);
+                }
+                Some(character) => text.push(character),
+                None => return Err(self.error("chaîne non terminée")),
+            }
+        }
+    }
+}
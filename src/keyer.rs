@@ -0,0 +1,630 @@
+use std::time::Duration;
+
+use crate::{
+    timeline::{scaled, Element, ElementKind, Tone},
+    timing::wpm_to_dit,
+};
+
+/// Comportement d'un manipulateur électronique.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyerMode {
+    /// Iambique mode A : tant que les deux palettes sont serrées, points et tirets alternent ;
+    /// l'alternance s'arrête à la fin de l'élément en cours dès qu'elles sont relâchées.
+    IambicA,
+    /// Iambique mode B : comme le mode A, mais un élément opposé supplémentaire est envoyé si
+    /// les palettes étaient serrées pendant l'élément au cours duquel elles sont relâchées.
+    IambicB,
+    /// Ultimatic : lorsque les deux palettes sont serrées, la dernière enfoncée l'emporte et son
+    /// élément est répété.
+    Ultimatic,
+    /// Manipulateur semi-automatique (« bug ») : la palette des points produit des points
+    /// automatiques, celle des tirets ferme la clé tant qu'elle est enfoncée.
+    Bug,
+}
+
+/// Palette d'un manipulateur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Paddle {
+    /// Palette des points.
+    Dit,
+    /// Palette des tirets.
+    Dah,
+}
+
+impl Paddle {
+    fn opposite(self) -> Paddle {
+        match self {
+            Paddle::Dit => Paddle::Dah,
+            Paddle::Dah => Paddle::Dit,
+        }
+    }
+
+    fn kind(self) -> ElementKind {
+        match self {
+            Paddle::Dit => ElementKind::Dot,
+            Paddle::Dah => ElementKind::Dash,
+        }
+    }
+}
+
+/// Appui ou relâchement d'une palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaddleEvent {
+    /// Palette concernée.
+    pub paddle: Paddle,
+    /// `true` pour un appui, `false` pour un relâchement.
+    pub pressed: bool,
+    /// Instant de l'événement.
+    pub at: Duration,
+}
+
+impl PaddleEvent {
+    /// Crée un appui sur une palette.
+    pub fn press(paddle: Paddle, at: Duration) -> Self {
+        PaddleEvent {
+            paddle,
+            pressed: true,
+            at,
+        }
+    }
+
+    /// Crée un relâchement d'une palette.
+    pub fn release(paddle: Paddle, at: Duration) -> Self {
+        PaddleEvent {
+            paddle,
+            pressed: false,
+            at,
+        }
+    }
+}
+
+/// Signal produit par un manipulateur : un point ou un tiret et son intervalle de temps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyedElement {
+    /// `ElementKind::Dot` ou `ElementKind::Dash`.
+    pub kind: ElementKind,
+    /// Instant où la clé se ferme.
+    pub start: Duration,
+    /// Instant où la clé s'ouvre.
+    pub end: Duration,
+}
+
+/// État des palettes au cours de la simulation.
+#[derive(Debug, Clone, Copy, Default)]
+struct Paddles {
+    dit: bool,
+    dah: bool,
+    /// Dernière palette enfoncée.
+    last: Option<Paddle>,
+}
+
+impl Paddles {
+    fn apply(&mut self, event: &PaddleEvent) {
+        match event.paddle {
+            Paddle::Dit => self.dit = event.pressed,
+            Paddle::Dah => self.dah = event.pressed,
+        }
+        if event.pressed {
+            self.last = Some(event.paddle);
+        }
+    }
+
+    fn is_down(&self, paddle: Paddle) -> bool {
+        match paddle {
+            Paddle::Dit => self.dit,
+            Paddle::Dah => self.dah,
+        }
+    }
+}
+
+/// Manipulateur électronique simulé : transforme les appuis et relâchements des palettes en
+/// signaux, comme le ferait un manipulateur iambique, Ultimatic ou semi-automatique.
+///
+/// Chaque point ou tiret automatique est suivi d'un silence d'un point, pendant lequel les
+/// palettes continuent d'être lues. Avec la mémoire des points (ou des tirets), un appui sur
+/// la palette opposée pendant un élément est retenu, et l'élément correspondant est envoyé
+/// ensuite même si la palette a été relâchée entre-temps. La pondération allonge (ou raccourcit)
+/// les points et tirets automatiques aux dépens du silence qui les suit, sans changer leur
+/// cadence ; les tirets manuels d'un bug ne sont pas pondérés.
+///
+/// Les signaux produits peuvent être décodés (`timings` et `Morse::decode_timings`), joués ou
+/// exportés (`elements`, `Morse::play_elements_on` et `Morse::elements_to_audio`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyer {
+    mode: KeyerMode,
+    dit: Duration,
+    dah_ratio: f32,
+    weight: f32,
+    dit_memory: bool,
+    dah_memory: bool,
+    tone: Tone,
+}
+
+impl Keyer {
+    /// Crée un manipulateur réglé à une vitesse donnée, avec la mémoire des points et des
+    /// tirets, un rapport tiret/point de 3, une pondération de 50 % et une tonalité de 600 Hz.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Le comportement du manipulateur.
+    /// * `wpm` - La vitesse en mots par minute.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{ElementKind, Keyer, KeyerMode, Paddle, PaddleEvent};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let ms = Duration::from_millis;
+    ///     // Palettes serrées (tiret d'abord) puis relâchées pendant le premier point
+    ///     let squeeze = [
+    ///         PaddleEvent::press(Paddle::Dah, ms(0)),
+    ///         PaddleEvent::press(Paddle::Dit, ms(10)),
+    ///         PaddleEvent::release(Paddle::Dah, ms(250)),
+    ///         PaddleEvent::release(Paddle::Dit, ms(250)),
+    ///     ];
+    ///     let kinds = |mode| -> Vec<ElementKind> {
+    ///         Keyer::new(mode, 20.0).key(&squeeze).iter().map(|element| element.kind).collect()
+    ///     };
+    ///     // Le mode A envoie N (-.), le mode B ajoute un tiret : K (-.-)
+    ///     assert_eq!(kinds(KeyerMode::IambicA), [ElementKind::Dash, ElementKind::Dot]);
+    ///     assert_eq!(
+    ///         kinds(KeyerMode::IambicB),
+    ///         [ElementKind::Dash, ElementKind::Dot, ElementKind::Dash]
+    ///     );
+    ///
+    ///     // À 20 mots par minute : tiret de 180 ms, silence de 60 ms, puis le point
+    ///     let elements = Keyer::new(KeyerMode::IambicB, 20.0).key(&squeeze);
+    ///     assert_eq!((elements[0].start, elements[0].end), (ms(0), ms(180)));
+    ///     assert_eq!((elements[1].start, elements[1].end), (ms(240), ms(300)));
+    /// }
+    /// ```
+    pub fn new(mode: KeyerMode, wpm: f32) -> Self {
+        let mut keyer = Keyer {
+            mode,
+            dit: Duration::ZERO,
+            dah_ratio: 3.0,
+            weight: 50.0,
+            dit_memory: true,
+            dah_memory: true,
+            tone: Tone::new(600.0, Duration::ZERO, 0.5),
+        };
+        keyer.set_wpm(wpm);
+        keyer
+    }
+
+    /// Crée un manipulateur avec une durée de point, un rapport tiret/point, une pondération et
+    /// une tonalité.
+    pub(crate) fn with_timing(
+        mode: KeyerMode,
+        dit: Duration,
+        dah_ratio: f32,
+        weight: f32,
+        tone: Tone,
+    ) -> Self {
+        Keyer {
+            mode,
+            dit,
+            dah_ratio,
+            weight,
+            dit_memory: true,
+            dah_memory: true,
+            tone,
+        }
+    }
+
+    /// Change la vitesse du manipulateur, en mots par minute (ramenée entre 1 et 200, comme
+    /// pour `Morse::set_wpm`).
+    pub fn set_wpm(&mut self, wpm: f32) {
+        self.dit = wpm_to_dit(wpm);
+    }
+
+    /// Change le rapport entre la durée d'un tiret et celle d'un point (au moins 1.0).
+    pub fn set_dah_ratio(&mut self, dah_ratio: f32) {
+        self.dah_ratio = dah_ratio.max(1.0);
+    }
+
+    /// Change la pondération des éléments automatiques, en pourcentage (ramenée entre 10 et 90).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Keyer, KeyerMode, Paddle, PaddleEvent};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let ms = Duration::from_millis;
+    ///     let mut keyer = Keyer::new(KeyerMode::IambicB, 20.0);
+    ///     keyer.set_weight(60.0);
+    ///     let elements = keyer.key(&[
+    ///         PaddleEvent::press(Paddle::Dit, ms(0)),
+    ///         PaddleEvent::release(Paddle::Dit, ms(150)),
+    ///     ]);
+    ///     // Le point dure 72 ms au lieu de 60, le suivant part toujours à 120 ms
+    ///     assert_eq!((elements[0].end, elements[1].start), (ms(72), ms(120)));
+    /// }
+    /// ```
+    pub fn set_weight(&mut self, weight: f32) {
+        self.weight = weight.clamp(10.0, 90.0);
+    }
+
+    /// Active ou désactive la mémoire des points et celle des tirets.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Keyer, KeyerMode, Paddle, PaddleEvent};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let ms = Duration::from_millis;
+    ///     // Un point est tapé brièvement pendant un tiret
+    ///     let events = [
+    ///         PaddleEvent::press(Paddle::Dah, ms(0)),
+    ///         PaddleEvent::press(Paddle::Dit, ms(50)),
+    ///         PaddleEvent::release(Paddle::Dit, ms(100)),
+    ///         PaddleEvent::release(Paddle::Dah, ms(150)),
+    ///     ];
+    ///     let mut keyer = Keyer::new(KeyerMode::IambicA, 20.0);
+    ///     assert_eq!(keyer.key(&events).len(), 2);
+    ///     keyer.set_memory(false, true);
+    ///     assert_eq!(keyer.key(&events).len(), 1);
+    /// }
+    /// ```
+    pub fn set_memory(&mut self, dit_memory: bool, dah_memory: bool) {
+        self.dit_memory = dit_memory;
+        self.dah_memory = dah_memory;
+    }
+
+    /// Change la tonalité des éléments produits par `elements`.
+    pub fn set_tone(&mut self, tone: Tone) {
+        self.tone = tone;
+    }
+
+    /// Simule le manipulateur et retourne les signaux produits, dans l'ordre.
+    ///
+    /// La simulation s'arrête au dernier événement : une palette encore enfoncée à cet instant
+    /// est considérée comme relâchée (voir `key_until` pour prolonger la simulation).
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - Les appuis et relâchements des palettes (triés par instant s'ils ne le
+    ///   sont pas déjà).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{ElementKind, Keyer, KeyerMode, Paddle, PaddleEvent};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let ms = Duration::from_millis;
+    ///     // Points tenus, puis tirets ajoutés par-dessus
+    ///     let events = [
+    ///         PaddleEvent::press(Paddle::Dit, ms(0)),
+    ///         PaddleEvent::press(Paddle::Dah, ms(100)),
+    ///         PaddleEvent::release(Paddle::Dit, ms(400)),
+    ///         PaddleEvent::release(Paddle::Dah, ms(400)),
+    ///     ];
+    ///     let code = |mode| -> String {
+    ///         Keyer::new(mode, 20.0)
+    ///             .key(&events)
+    ///             .iter()
+    ///             .map(|element| if element.kind == ElementKind::Dot { '.' } else { '-' })
+    ///             .collect()
+    ///     };
+    ///     assert_eq!(code(KeyerMode::IambicA), ".-.");
+    ///     assert_eq!(code(KeyerMode::IambicB), ".-.-");
+    ///     // La dernière palette enfoncée l'emporte
+    ///     assert_eq!(code(KeyerMode::Ultimatic), ".--");
+    ///
+    ///     // Bug : points automatiques, puis tiret manuel aussi long que l'appui
+    ///     let bug = Keyer::new(KeyerMode::Bug, 20.0).key(&[
+    ///         PaddleEvent::press(Paddle::Dit, ms(0)),
+    ///         PaddleEvent::release(Paddle::Dit, ms(300)),
+    ///         PaddleEvent::press(Paddle::Dah, ms(500)),
+    ///         PaddleEvent::release(Paddle::Dah, ms(900)),
+    ///     ]);
+    ///     assert_eq!(bug.len(), 4);
+    ///     assert_eq!(bug[2].start, ms(240));
+    ///     assert_eq!((bug[3].kind, bug[3].start, bug[3].end), (ElementKind::Dash, ms(500), ms(900)));
+    /// }
+    /// ```
+    pub fn key(&self, events: &[PaddleEvent]) -> Vec<KeyedElement> {
+        let until: Duration = events
+            .iter()
+            .map(|event| event.at)
+            .max()
+            .unwrap_or(Duration::ZERO);
+        self.key_until(events, until)
+    }
+
+    /// Simule le manipulateur jusqu'à un instant donné, par exemple l'instant présent d'une
+    /// manipulation en direct. Les événements postérieurs à `until` sont ignorés, et les
+    /// palettes encore enfoncées sont considérées comme relâchées à cet instant : l'élément en
+    /// cours est terminé (ainsi que celui qui a été mémorisé) et un tiret manuel s'arrête.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - Les appuis et relâchements des palettes.
+    /// * `until` - L'instant où la simulation s'arrête.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Keyer, KeyerMode, Paddle, PaddleEvent};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let ms = Duration::from_millis;
+    ///     // La palette des points est enfoncée et n'a pas encore été relâchée
+    ///     let held = [PaddleEvent::press(Paddle::Dit, ms(0))];
+    ///     let keyer = Keyer::new(KeyerMode::IambicA, 20.0);
+    ///     assert_eq!(keyer.key(&held).len(), 1);
+    ///     // Un point toutes les 120 ms : à 0, 120, 240, 360 et 480 ms
+    ///     assert_eq!(keyer.key_until(&held, ms(500)).len(), 5);
+    ///
+    ///     let bug = Keyer::new(KeyerMode::Bug, 20.0);
+    ///     assert_eq!(bug.key(&held).len(), 1);
+    ///     // Avec un bug, le tiret manuel s'arrête à l'horizon
+    ///     let dash = bug.key_until(&[PaddleEvent::press(Paddle::Dah, ms(100))], ms(400));
+    ///     assert_eq!((dash[0].start, dash[0].end), (ms(100), ms(400)));
+    /// }
+    /// ```
+    pub fn key_until(&self, events: &[PaddleEvent], until: Duration) -> Vec<KeyedElement> {
+        let mut events: Vec<PaddleEvent> = events
+            .iter()
+            .filter(|event| event.at <= until)
+            .copied()
+            .collect();
+        events.sort_by_key(|event| event.at);
+        let mut paddles: Paddles = Paddles::default();
+        events.iter().for_each(|event| paddles.apply(event));
+        for paddle in [Paddle::Dit, Paddle::Dah] {
+            if paddles.is_down(paddle) {
+                events.push(PaddleEvent::release(paddle, until));
+            }
+        }
+        match self.mode {
+            KeyerMode::Bug => self.key_bug(&events),
+            _ => self.key_automatic(&events),
+        }
+    }
+
+    /// Retourne les durées des signaux et des silences produits, au format de
+    /// `Morse::decode_timings` (`true` pour un signal).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{KeyerMode, Morse, Paddle, PaddleEvent};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let ms = Duration::from_millis;
+    ///     let mut morse = Morse::new();
+    ///     morse.set_wpm(20.0);
+    ///     // C (-.-.) aux palettes serrées, une pause, puis Q (--.-)
+    ///     let keyer = morse.keyer(KeyerMode::IambicA);
+    ///     let timings = keyer.timings(&[
+    ///         PaddleEvent::press(Paddle::Dah, ms(0)),
+    ///         PaddleEvent::press(Paddle::Dit, ms(30)),
+    ///         PaddleEvent::release(Paddle::Dah, ms(620)),
+    ///         PaddleEvent::release(Paddle::Dit, ms(620)),
+    ///         PaddleEvent::press(Paddle::Dah, ms(850)),
+    ///         PaddleEvent::release(Paddle::Dah, ms(1250)),
+    ///         PaddleEvent::press(Paddle::Dit, ms(1300)),
+    ///         PaddleEvent::press(Paddle::Dah, ms(1410)),
+    ///         PaddleEvent::release(Paddle::Dit, ms(1410)),
+    ///         PaddleEvent::release(Paddle::Dah, ms(1500)),
+    ///     ]);
+    ///     assert_eq!(morse.decode_timings(&timings).text, "CQ");
+    /// }
+    /// ```
+    pub fn timings(&self, events: &[PaddleEvent]) -> Vec<(bool, Duration)> {
+        let mut timings: Vec<(bool, Duration)> = Vec::new();
+        let mut previous: Option<Duration> = None;
+        for element in self.key(events) {
+            if let Some(end) = previous {
+                timings.push((false, element.start.saturating_sub(end)));
+            }
+            timings.push((true, element.end - element.start));
+            previous = Some(element.end);
+        }
+        timings
+    }
+
+    /// Retourne les éléments à jouer ou à exporter : les signaux avec la tonalité du
+    /// manipulateur, séparés par des silences (pauses de moins de deux points, silences au-delà).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Element, KeyerMode, MemorySink, Morse, Paddle, PaddleEvent};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let ms = Duration::from_millis;
+    ///     let mut morse = Morse::new();
+    ///     morse.set_wpm(20.0);
+    ///     let elements: Vec<Element> = morse.keyer(KeyerMode::IambicB).elements(&[
+    ///         PaddleEvent::press(Paddle::Dit, ms(0)),
+    ///         PaddleEvent::release(Paddle::Dit, ms(150)),
+    ///     ]);
+    ///     // Deux points séparés par un silence d'un point
+    ///     assert_eq!(elements.len(), 3);
+    ///
+    ///     let mut sink = MemorySink::new();
+    ///     let Ok(()) = morse.play_elements_on(&elements, &mut sink);
+    ///     assert_eq!(sink.duration(), ms(180));
+    ///     morse.elements_to_audio(&elements, "palettes.wav").unwrap();
+    ///     std::fs::remove_file("palettes.wav").unwrap();
+    /// }
+    /// ```
+    pub fn elements(&self, events: &[PaddleEvent]) -> Vec<Element> {
+        let mut elements: Vec<Element> = Vec::new();
+        let mut previous: Option<Duration> = None;
+        for element in self.key(events) {
+            if let Some(end) = previous {
+                let gap: Duration = element.start.saturating_sub(end);
+                let kind: ElementKind = if gap < self.dit * 2 {
+                    ElementKind::Pause
+                } else {
+                    ElementKind::Silence
+                };
+                elements.push(Element::new(kind, Tone::silence(gap)));
+            }
+            let tone: Tone = Tone {
+                duration: element.end - element.start,
+                ..self.tone
+            };
+            elements.push(Element::new(element.kind, tone));
+            previous = Some(element.end);
+        }
+        elements
+    }
+
+    /// Simule un manipulateur iambique ou Ultimatic.
+    fn key_automatic(&self, events: &[PaddleEvent]) -> Vec<KeyedElement> {
+        let mut elements: Vec<KeyedElement> = Vec::new();
+        let mut paddles: Paddles = Paddles::default();
+        let mut index: usize = 0;
+        // Prochain élément à envoyer et son début
+        let mut next: Option<(Paddle, Duration)> = None;
+        loop {
+            let (paddle, start) = match next.take() {
+                Some(next) => next,
+                None => {
+                    // Au repos : attend le prochain appui
+                    let Some(event) = events.get(index) else {
+                        break;
+                    };
+                    let at: Duration = event.at;
+                    // Un appui relâché au même instant lance tout de même un élément
+                    let mut tapped: Option<Paddle> = None;
+                    while index < events.len() && events[index].at == at {
+                        paddles.apply(&events[index]);
+                        if events[index].pressed {
+                            tapped = tapped.or(Some(events[index].paddle));
+                        }
+                        index += 1;
+                    }
+                    let paddle: Paddle = match (paddles.dit, paddles.dah) {
+                        (true, true) if self.mode == KeyerMode::Ultimatic => {
+                            paddles.last.unwrap_or(Paddle::Dit)
+                        }
+                        (true, _) => Paddle::Dit,
+                        (false, true) => Paddle::Dah,
+                        (false, false) => match tapped {
+                            Some(paddle) => paddle,
+                            None => continue,
+                        },
+                    };
+                    (paddle, at)
+                }
+            };
+            let mark: Duration = match paddle {
+                Paddle::Dit => self.dit,
+                Paddle::Dah => scaled(self.dit, self.dah_ratio),
+            };
+            let end: Duration = start + mark + self.dit;
+            elements.push(KeyedElement {
+                kind: paddle.kind(),
+                start,
+                end: start + self.weighted(mark),
+            });
+
+            // Les palettes sont lues pendant l'élément et le silence qui le suit
+            let opposite: Paddle = paddle.opposite();
+            let mut remembered: bool = false;
+            let mut squeezed: bool = paddles.dit && paddles.dah;
+            while index < events.len() && events[index].at < end {
+                let event: PaddleEvent = events[index];
+                paddles.apply(&event);
+                index += 1;
+                remembered |= event.pressed && event.paddle == opposite && self.memory(opposite);
+                squeezed |= paddles.dit && paddles.dah;
+            }
+            if self.mode == KeyerMode::IambicB && squeezed {
+                remembered = true;
+            }
+
+            let following: Option<Paddle> = match self.mode {
+                KeyerMode::Ultimatic if paddles.dit && paddles.dah => paddles.last,
+                KeyerMode::Ultimatic if remembered => Some(opposite),
+                KeyerMode::Ultimatic => [Paddle::Dit, Paddle::Dah]
+                    .into_iter()
+                    .find(|paddle| paddles.is_down(*paddle)),
+                _ if remembered || paddles.is_down(opposite) => Some(opposite),
+                _ => Some(paddle).filter(|paddle| paddles.is_down(*paddle)),
+            };
+            next = following.map(|paddle| (paddle, end));
+        }
+        elements
+    }
+
+    /// Simule un manipulateur semi-automatique.
+    fn key_bug(&self, events: &[PaddleEvent]) -> Vec<KeyedElement> {
+        let mut elements: Vec<KeyedElement> = Vec::new();
+        let mut paddles: Paddles = Paddles::default();
+        let mut index: usize = 0;
+        let mut now: Duration = Duration::ZERO;
+        loop {
+            let mut tapped: bool = false;
+            while index < events.len() && events[index].at <= now {
+                paddles.apply(&events[index]);
+                tapped |= events[index].pressed && events[index].paddle == Paddle::Dit;
+                index += 1;
+            }
+            if paddles.dah {
+                // Tiret manuel : la clé reste fermée jusqu'au relâchement de la palette
+                let release: Duration = events[index..]
+                    .iter()
+                    .find(|event| event.paddle == Paddle::Dah && !event.pressed)
+                    .map_or(now, |event| event.at);
+                if release > now {
+                    elements.push(KeyedElement {
+                        kind: ElementKind::Dash,
+                        start: now,
+                        end: release,
+                    });
+                    now = release;
+                } else {
+                    paddles.dah = false;
+                }
+            } else if paddles.dit || tapped {
+                // Points automatiques, chacun suivi d'un silence d'un point
+                elements.push(KeyedElement {
+                    kind: ElementKind::Dot,
+                    start: now,
+                    end: now + self.weighted(self.dit),
+                });
+                now += self.dit * 2;
+            } else {
+                let Some(event) = events.get(index) else {
+                    break;
+                };
+                now = now.max(event.at);
+            }
+        }
+        elements
+    }
+
+    /// Applique la pondération à la durée d'un élément automatique.
+    fn weighted(&self, mark: Duration) -> Duration {
+        let shift: Duration = scaled(self.dit, (self.weight - 50.0).abs() / 50.0);
+        if self.weight >= 50.0 {
+            mark + shift
+        } else {
+            mark.saturating_sub(shift)
+        }
+    }
+
+    fn memory(&self, paddle: Paddle) -> bool {
+        match paddle {
+            Paddle::Dit => self.dit_memory,
+            Paddle::Dah => self.dah_memory,
+        }
+    }
+}
@@ -1,2 +1,44 @@
+mod abbreviations;
+mod audio;
+mod audio_decoder;
+mod drill;
+mod json;
+mod keyer;
+mod light_decoder;
 mod morse;
-pub use morse::Morse;
\ No newline at end of file
+#[cfg(feature = "playback")]
+mod player;
+mod qso;
+mod rng;
+mod scoring;
+#[cfg(feature = "playback")]
+mod sidetone;
+mod sink;
+mod skimmer;
+mod source;
+mod spectrum;
+mod timeline;
+mod timing;
+mod training;
+pub use abbreviations::{compress_abbreviations, expand_abbreviations, Compression};
+pub use audio::{from_dbfs, to_dbfs, ClippingPolicy, ExportReport, Normalization};
+pub use audio_decoder::{DetectedTone, StreamingAudioDecoder, ToneDetection};
+pub use drill::{IcrCharacterStatistics, IcrDrill, IcrPrompt, IcrStatistics, IcrTrial};
+pub use keyer::{KeyedElement, Keyer, KeyerMode, Paddle, PaddleEvent};
+pub use morse::Morse;
+#[cfg(feature = "playback")]
+pub use player::{Player, PlayerError, PlayerEvent};
+pub use qso::{Contest, Qso, QsoGenerator, Station};
+pub use scoring::{score_copy, CharacterScore, Confusion, CopyScore, Edit};
+#[cfg(feature = "playback")]
+pub use sidetone::Sidetone;
+#[cfg(feature = "playback")]
+pub use sink::RodioSink;
+pub use sink::{AudioSink, MemorySink, NullSink, WavSink};
+pub use skimmer::{SkimmedCharacter, Skimmer};
+pub use source::MorseSource;
+pub use timeline::{
+    CharacterDuration, Element, ElementKind, Marker, MessageDuration, Subtitle, Tone,
+};
+pub use timing::{Alternative, DecodedCharacter, DecodedMessage, StreamingTimingDecoder};
+pub use training::{CharacterProgress, Koch, Lesson, ProgressError, Trainer, KOCH_ORDER};
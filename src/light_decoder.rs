@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+/// Convertit une suite de mesures de luminosité en durées de manipulation (`true` pour une
+/// lumière allumée, `false` pour une lumière éteinte).
+///
+/// Les niveaux « éteint » et « allumé » sont estimés par le 10e et le 90e centile des mesures.
+/// La lumière est considérée comme allumée au-delà de 60 % de l'écart entre ces niveaux, et
+/// éteinte en deçà de 40 % : cette hystérésis évite que le bruit du capteur ou de la
+/// compression vidéo ne crée des clignotements parasites. Les mesures non finies sont
+/// ignorées, et une suite sans contraste ne produit aucune durée.
+pub(crate) fn brightness_runs(brightness: &[f32], frame_rate: f32) -> Vec<(bool, Duration)> {
+    if !(frame_rate.is_finite() && frame_rate > 0.0) {
+        return Vec::new();
+    }
+    let mut sorted: Vec<f32> = brightness
+        .iter()
+        .copied()
+        .filter(|value| value.is_finite())
+        .collect();
+    sorted.sort_by(f32::total_cmp);
+    let percentile = |p: f32| -> f32 {
+        sorted
+            .get(((sorted.len() as f32 - 1.0) * p) as usize)
+            .copied()
+            .unwrap_or(0.0)
+    };
+    let (dark, bright) = (percentile(0.10), percentile(0.90));
+    let contrast: f32 = bright - dark;
+    if contrast <= f32::EPSILON * bright.abs().max(1.0) {
+        return Vec::new();
+    }
+    let (on, off) = (dark + 0.6 * contrast, dark + 0.4 * contrast);
+
+    let mut runs: Vec<(bool, usize)> = Vec::new();
+    let mut lit: bool = false;
+    for &value in brightness {
+        if value.is_finite() {
+            if lit && value < off {
+                lit = false;
+            } else if !lit && value > on {
+                lit = true;
+            }
+        }
+        match runs.last_mut() {
+            Some((last, frames)) if *last == lit => *frames += 1,
+            _ => runs.push((lit, 1)),
+        }
+    }
+    runs.into_iter()
+        .map(|(lit, frames)| {
+            (
+                lit,
+                Duration::from_secs_f64(frames as f64 / frame_rate as f64),
+            )
+        })
+        .collect()
+}
@@ -1,25 +1,74 @@
-use hound::{Error as HoundError, WavSpec, WavWriter};
+use hound::{Error as HoundError, SampleFormat, WavReader, WavSpec, WavWriter};
 
-use rodio::{
-    source::{Amplify, SineWave, Source, TakeDuration},
-    OutputStream, Sink,
+#[cfg(feature = "playback")]
+use rodio::{OutputStream, Sink};
+
+#[cfg(feature = "playback")]
+use crate::{
+    player::{Player, PlayerError},
+    sidetone::Sidetone,
+    sink::RodioSink,
+    timeline::Steps,
 };
 
 use std::{
-    collections::HashSet,
-    fs::File,
-    io::BufWriter,
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    io::{BufWriter, Seek, Write},
     str::{Split, SplitWhitespace},
     time::Duration,
 };
 
+use crate::{
+    audio::{
+        limit, process_samples, quantize, ClippingPolicy, ExportReport, LevelMeter, Normalization,
+    },
+    audio_decoder::{
+        detect_tone, envelope, key_runs, read_wav, DetectedTone, StreamingAudioDecoder,
+        ToneDetection, ToneTracker, MIN_TONE_SNR,
+    },
+    keyer::{Keyer, KeyerMode},
+    light_decoder::brightness_runs,
+    rng::Rng,
+    sink::AudioSink,
+    skimmer::{channel_runs, Skimmer},
+    source::MorseSource,
+    timeline::{
+        message_duration, prosign, scaled, subtitles, Element, MessageDuration, MorseCodeSteps,
+        Step, Subtitle, TextSteps, Tone, Tones,
+    },
+    timing::{
+        characters_to_morse, decode_runs, dit_to_wpm, wpm_to_dit, DecodedMessage,
+        StreamingTimingDecoder, TimingTracker, WPM_RANGE,
+    },
+};
+
+/// Signes de procédure reconnus au décodage lorsque leur code ne correspond à aucun caractère
+/// (AR, BT, KN et AS sont décodés en '+', '=', '(' et '&').
+const PROSIGNS: [&str; 8] = ["SK", "BK", "CL", "KA", "SN", "AA", "HH", "SOS"];
+
+#[derive(Clone)]
 pub struct Morse {
     morse_code: HashSet<(char, String)>,
-    short_beep: Amplify<TakeDuration<SineWave>>,
-    long_beep: Amplify<TakeDuration<SineWave>>,
-    silence: Amplify<TakeDuration<SineWave>>,
+    short_beep: Tone,
+    long_beep: Tone,
+    silence: Tone,
+    word_silence: Tone,
     duration: Duration,
+    dah_ratio: f32,
+    weight: f32,
     sampling_rate: u32,
+    clipping_policy: ClippingPolicy,
+    normalization: Normalization,
+    bits_per_sample: u16,
+    dither: bool,
+    tone_detection: ToneDetection,
+}
+
+impl Default for Morse {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Morse {
@@ -30,7 +79,7 @@ impl Morse {
     /// ```rust
     /// use crate::morseus::Morse;
     /// fn main() {
-    /// 	let morse = Morse::new();
+    ///     let morse = Morse::new();
     /// }
     /// ```
     pub fn new() -> Self {
@@ -91,23 +140,25 @@ impl Morse {
         morse_code.insert(('@', ".--.-.".to_string()));
         morse_code.insert((' ', "/".to_string()));
 
-        let short_beep: Amplify<TakeDuration<SineWave>> = SineWave::new(329.63)
-            .take_duration(std::time::Duration::from_secs_f32(0.5))
-            .amplify(0.20);
-        let long_beep: Amplify<TakeDuration<SineWave>> = SineWave::new(392.0)
-            .take_duration(std::time::Duration::from_secs_f32(1.))
-            .amplify(0.20);
-        let silence: Amplify<TakeDuration<SineWave>> = SineWave::new(0.0)
-            .take_duration(std::time::Duration::from_secs_f32(1.))
-            .amplify(0.20);
+        let short_beep: Tone = Tone::new(329.63, Duration::from_secs_f32(0.5), 0.20);
+        let long_beep: Tone = Tone::new(392.0, Duration::from_secs_f32(1.), 0.20);
+        let silence: Tone = Tone::new(0.0, Duration::from_secs_f32(1.), 0.20);
 
         Morse {
             morse_code,
             short_beep,
             long_beep,
             silence,
+            word_silence: silence,
             duration: Duration::from_secs_f32(0.5),
+            dah_ratio: 3.0,
+            weight: 50.0,
             sampling_rate: 44100,
+            clipping_policy: ClippingPolicy::HardClip,
+            normalization: Normalization::None,
+            bits_per_sample: 16,
+            dither: false,
+            tone_detection: ToneDetection::Fixed,
         }
     }
 
@@ -125,16 +176,29 @@ impl Morse {
     ///     let morse = Morse::new();
     ///     let encoded_text = morse.encode("HELLO WORLD");
     ///     assert_eq!(encoded_text, ".... . .-.. .-.. --- / .-- --- .-. .-.. -..");
+    ///     // Les signes de procédure s'écrivent entre chevrons
+    ///     assert_eq!(morse.encode("73 <SK>"), "--... ...-- / ...-.-");
     /// }
     /// ```
     pub fn encode(&self, text: &str) -> String {
         let mut encoded: String = String::new();
 
-        for c in text.chars() {
-            if let Some(code) = self.get_morse_code(c) {
+        let mut position: usize = 0;
+        while let Some(c) = text[position..].chars().next() {
+            // Un signe de procédure comme <SK> est encodé sans espace entre ses lettres
+            if let Some((code, length)) =
+                prosign(&text[position..], |c| self.get_morse_code(c).cloned())
+            {
                 encoded.push_str(&code);
+                encoded.push(' ');
+                position += length;
+                continue;
+            }
+            if let Some(code) = self.get_morse_code(c) {
+                encoded.push_str(code);
                 encoded.push(' '); // espace entre les caractères encodés
             }
+            position += c.len_utf8();
         }
 
         encoded.trim().to_string() // supprime l'espace final et retourne le résultat
@@ -165,6 +229,8 @@ impl Morse {
             for character in characters {
                 if let Some(ch) = self.get_morse_character(character) {
                     decoded.push(ch);
+                } else if let Some(prosign) = self.get_prosign(character) {
+                    decoded.push_str(&format!("<{}>", prosign));
                 }
             }
             decoded.push(' '); // espace entre les mots décodés
@@ -173,6 +239,31 @@ impl Morse {
         decoded.trim().to_string() // supprime l'espace final et retourne le résultat
     }
 
+    /// Recherche le signe de procédure (sans chevrons) associé à un code Morse qui ne correspond
+    /// à aucun caractère, comme `...-.-` pour `SK`.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     assert_eq!(morse.get_prosign("...-.-"), Some("SK"));
+    ///     assert_eq!(morse.decode("--... ...-- / ...-.-"), "73 <SK>");
+    /// }
+    /// ```
+    pub fn get_prosign(&self, code: &str) -> Option<&'static str> {
+        PROSIGNS.into_iter().find(|prosign| {
+            self.get_morse_character(code).is_none()
+                && prosign
+                    .chars()
+                    .map(|letter| self.get_morse_code(letter).cloned())
+                    .collect::<Option<String>>()
+                    .is_some_and(|prosign_code| prosign_code == code)
+        })
+    }
+
     /// Vérifie si une chaîne de texte est constituée uniquement de caractères valides en code Morse (".", "-", "/").
     ///
     /// # Arguments
@@ -282,21 +373,22 @@ impl Morse {
     ///
     /// # Exemple
     ///
-    /// ```rust
+    /// ```no_run
     /// use crate::morseus::Morse;
     ///
     /// fn main() {
     ///     let morse = Morse::new();
-    /// 	// Exemple de bip court
-    ///		println!("Exemple de bip court :");
-    ///		morse.play_short_beep();
+    ///     // Exemple de bip court
+    ///     println!("Exemple de bip court :");
+    ///     morse.play_short_beep();
     /// }
     /// ```
+    #[cfg(feature = "playback")]
     pub fn play_short_beep(&self) {
         let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-        let sink : Sink = Sink::try_new(&stream_handle).unwrap();
+        let sink: Sink = Sink::try_new(&stream_handle).unwrap();
 
-        sink.append(self.short_beep.clone());
+        sink.append(self.source_of(self.tones().element('.')));
         sink.sleep_until_end();
     }
 
@@ -307,21 +399,22 @@ impl Morse {
     ///
     /// # Exemple
     ///
-    /// ```rust
+    /// ```no_run
     /// use crate::morseus::Morse;
     ///
     /// fn main() {
     ///     let morse = Morse::new();
-    /// 	// Exemple de bip long
-    ///		println!("Exemple de bip long :");
-    ///		morse.play_long_beep();
+    ///     // Exemple de bip long
+    ///     println!("Exemple de bip long :");
+    ///     morse.play_long_beep();
     /// }
     /// ```
+    #[cfg(feature = "playback")]
     pub fn play_long_beep(&self) {
         let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-        let sink : Sink = Sink::try_new(&stream_handle).unwrap();
+        let sink: Sink = Sink::try_new(&stream_handle).unwrap();
 
-        sink.append(self.long_beep.clone());
+        sink.append(self.source_of(self.tones().element('-')));
         sink.sleep_until_end();
     }
 
@@ -331,21 +424,22 @@ impl Morse {
     ///
     /// # Exemple
     ///
-    /// ```rust
+    /// ```no_run
     /// use crate::morseus::Morse;
     ///
     /// fn main() {
     ///     let morse = Morse::new();
-    /// 	// Exemple de bip long
-    ///		println!("Exemple de bip silencieux :");
-    ///		morse.play_silence();
+    ///     // Exemple de bip long
+    ///     println!("Exemple de bip silencieux :");
+    ///     morse.play_silence();
     /// }
     /// ```
+    #[cfg(feature = "playback")]
     pub fn play_silence(&self) {
         let (_stream, stream_handle) = OutputStream::try_default().unwrap();
-        let sink : Sink = Sink::try_new(&stream_handle).unwrap();
+        let sink: Sink = Sink::try_new(&stream_handle).unwrap();
 
-        sink.append(self.silence.clone());
+        sink.append(self.source_of(self.tones().element(' ')));
         sink.sleep_until_end();
     }
 
@@ -360,13 +454,11 @@ impl Morse {
     ///
     /// fn main() {
     ///     let mut morse = Morse::new();
-    ///		morse.set_short_beep(300., Duration::from_secs_f32(0.5), 0.20);
+    ///     morse.set_short_beep(300., Duration::from_secs_f32(0.5), 0.20);
     /// }
     /// ```
     pub fn set_short_beep(&mut self, frequency: f32, duration: Duration, amplification: f32) {
-        self.short_beep = SineWave::new(frequency)
-            .take_duration(duration)
-            .amplify(amplification);
+        self.short_beep = Tone::new(frequency, duration, amplification);
     }
 
     /// Change le son du un bip long.
@@ -380,18 +472,17 @@ impl Morse {
     ///
     /// fn main() {
     ///     let mut morse = Morse::new();
-    ///		morse.set_long_beep(300., Duration::from_secs_f32(0.5), 0.20);
+    ///     morse.set_long_beep(300., Duration::from_secs_f32(0.5), 0.20);
     /// }
     ///
     /// ```
     pub fn set_long_beep(&mut self, frequency: f32, duration: Duration, amplification: f32) {
-        self.long_beep = SineWave::new(frequency)
-            .take_duration(duration)
-            .amplify(amplification);
+        self.long_beep = Tone::new(frequency, duration, amplification);
     }
 
     /// Change le son du un bip silencieux.
     /// Cette fonction remplace le son à jouer lors d'un bip silencieux.   
+    /// Le même son sépare les caractères et les mots (voir `set_farnsworth` pour les distinguer).
     ///
     /// # Exemple
     ///
@@ -401,13 +492,12 @@ impl Morse {
     ///
     /// fn main() {
     ///     let mut morse = Morse::new();
-    ///		morse.set_silence(300., Duration::from_secs_f32(0.5), 0.20);
+    ///     morse.set_silence(300., Duration::from_secs_f32(0.5), 0.20);
     /// }
     ///
     pub fn set_silence(&mut self, frequency: f32, duration: Duration, amplification: f32) {
-        self.silence = SineWave::new(frequency)
-            .take_duration(duration)
-            .amplify(amplification);
+        self.silence = Tone::new(frequency, duration, amplification);
+        self.word_silence = self.silence;
     }
     /// Change le temps entre chaque son lorsqu'on joue le morse en audio.
     ///
@@ -419,7 +509,7 @@ impl Morse {
     ///
     /// fn main() {
     ///     let mut morse = Morse::new();
-    ///		morse.set_duration(Duration::from_secs_f32(0.5));
+    ///     morse.set_duration(Duration::from_secs_f32(0.5));
     /// }
     ///
     pub fn set_duration(&mut self, duration: Duration) {
@@ -435,17 +525,374 @@ impl Morse {
     ///
     /// fn main() {
     ///     let mut morse = Morse::new();
-    ///		morse.set_sampling_rate(8);
+    ///     morse.set_sampling_rate(8);
     /// }
     ///
     pub fn set_sampling_rate(&mut self, sampling_rate: u32) {
         self.sampling_rate = sampling_rate;
     }
 
+    /// Règle la vitesse de manipulation en mots par minute (norme « PARIS » : un point dure
+    /// 1,2 s / wpm).
+    ///
+    /// Le point, la pause entre les signaux et le silence qui sépare les caractères durent un
+    /// point, le tiret trois points (ou le rapport de `set_dah_ratio`) : un caractère est donc
+    /// suivi de trois points de silence et un mot de sept. Les fréquences et les amplitudes des
+    /// bips sont conservées.
+    ///
+    /// # Arguments
+    ///
+    /// * `wpm` - La vitesse en mots par minute, ramenée entre 1 et 200 (une vitesse nulle,
+    ///   négative ou NaN donne 1).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_wpm(20.0);
+    ///     let elements = morse.timeline(".-");
+    ///     assert_eq!(elements[0].tone.duration, Duration::from_millis(60));
+    ///     assert_eq!(elements[2].tone.duration, Duration::from_millis(180));
+    ///
+    ///     morse.set_wpm(0.0);
+    ///     assert_eq!(morse.timeline(".")[0].tone.duration, Duration::from_millis(1200));
+    /// }
+    /// ```
+    pub fn set_wpm(&mut self, wpm: f32) {
+        let dit: Duration = wpm_to_dit(wpm);
+        self.short_beep.duration = dit;
+        self.long_beep.duration = scaled(dit, self.dah_ratio);
+        self.silence.duration = dit;
+        self.word_silence = self.silence;
+        self.duration = dit;
+    }
+
+    /// Règle une vitesse Farnsworth : les caractères sont manipulés à `character_wpm`, mais
+    /// les silences entre les caractères et entre les mots sont allongés pour que le message
+    /// avance à `effective_wpm`.
+    ///
+    /// Les durées suivent la méthode de l'ARRL : le retard total `ta = (60c − 37,2s) / (s·c)`
+    /// est réparti pour 3/19 entre les caractères et pour 7/19 entre les mots. Si la vitesse
+    /// effective n'est pas inférieure à celle des caractères, le minutage standard de
+    /// `set_wpm` est utilisé.
+    ///
+    /// # Arguments
+    ///
+    /// * `character_wpm` - La vitesse des caractères en mots par minute, ramenée entre 1 et
+    ///   200 comme pour `set_wpm`.
+    /// * `effective_wpm` - La vitesse effective du message en mots par minute, ramenée dans
+    ///   la même plage.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_farnsworth(18.0, 5.0);
+    ///     let elements = morse.timeline(". / .");
+    ///     // Point, pause, espace, pause, séparateur de mots, pause, espace, pause, point
+    ///     assert_eq!(elements[0].tone.duration, Duration::from_nanos(66_666_667));
+    ///     let word_gap: Duration = elements[1..8].iter().map(|element| element.tone.duration).sum();
+    ///     let expected: f32 = 7.0 / 19.0 * (60.0 * 18.0 - 37.2 * 5.0) / (5.0 * 18.0);
+    ///     assert!((word_gap.as_secs_f32() - expected).abs() < 0.001);
+    ///
+    ///     // Le décodeur attend les silences allongés : les caractères ne sont pas pris pour
+    ///     // des mots
+    ///     morse.set_sampling_rate(8000);
+    ///     let samples: Vec<f32> = morse.render_samples(&morse.encode("CQ DE F4ABC"));
+    ///     assert_eq!(morse.decode_samples(&samples, 8000).text, "CQ DE F4ABC");
+    ///
+    ///     // Une vitesse effective nulle est ramenée à 1 mot par minute
+    ///     morse.set_farnsworth(20.0, 0.0);
+    ///     assert!(morse.duration_of("PARIS PARIS").duration > Duration::from_secs(60));
+    /// }
+    /// ```
+    pub fn set_farnsworth(&mut self, character_wpm: f32, effective_wpm: f32) {
+        self.set_wpm(character_wpm);
+        let character_wpm: f32 = character_wpm.max(WPM_RANGE.0).min(WPM_RANGE.1);
+        let effective_wpm: f32 = effective_wpm.max(WPM_RANGE.0).min(WPM_RANGE.1);
+        if effective_wpm >= character_wpm {
+            return;
+        }
+        let (c, s) = (character_wpm, effective_wpm);
+        let total_delay: f32 = (60.0 * c - 37.2 * s) / (s * c);
+        let character_gap: f32 = 3.0 * total_delay / 19.0;
+        let word_gap: f32 = 7.0 * total_delay / 19.0;
+        // Entre deux caractères : pause, espace, pause
+        let pause: f32 = self.duration.as_secs_f32();
+        self.silence.duration = Duration::from_secs_f32(character_gap - 2.0 * pause);
+        // Entre deux mots : pause, espace, pause, '/', pause, espace, pause
+        self.word_silence.duration = Duration::from_secs_f32(word_gap - 2.0 * character_gap);
+    }
+
+    /// Change le rapport entre la durée d'un tiret et celle d'un point (3.0 par défaut, comme
+    /// dans la norme). La durée du point est conservée : le tiret s'allonge ou se raccourcit.
+    ///
+    /// # Arguments
+    ///
+    /// * `dah_ratio` - Le rapport tiret/point, au moins 1.0.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_wpm(20.0);
+    ///     morse.set_dah_ratio(3.5);
+    ///     let elements = morse.timeline(".-");
+    ///     assert_eq!(elements[0].tone.duration, Duration::from_millis(60));
+    ///     assert_eq!(elements[2].tone.duration, Duration::from_millis(210));
+    ///
+    ///     // Le rapport est conservé lorsque la vitesse change
+    ///     morse.set_wpm(10.0);
+    ///     assert_eq!(morse.timeline("-")[0].tone.duration, Duration::from_millis(420));
+    /// }
+    /// ```
+    pub fn set_dah_ratio(&mut self, dah_ratio: f32) {
+        self.dah_ratio = dah_ratio.max(1.0);
+        self.long_beep.duration = scaled(self.short_beep.duration, self.dah_ratio);
+    }
+
+    /// Retourne le rapport entre la durée d'un tiret et celle d'un point.
+    pub fn dah_ratio(&self) -> f32 {
+        self.dah_ratio
+    }
+
+    /// Change la pondération de la manipulation, en pourcentage (50 par défaut).
+    ///
+    /// Au-delà de 50, chaque signal est allongé et le silence qui le suit raccourci d'autant,
+    /// pour un son plus « lourd » ou pour compenser la mise en forme d'un émetteur ; en deçà,
+    /// les signaux sont raccourcis. Un point et la pause qui le suit durent toujours deux
+    /// points : à 60 %, le point occupe 1,2 point et la pause 0,8. La vitesse du message est
+    /// donc conservée. La pondération s'applique à la lecture, à l'export WAV et à
+    /// `duration_of`.
+    ///
+    /// # Arguments
+    ///
+    /// * `weight` - La pondération, ramenée entre 10 et 90 %.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_wpm(20.0);
+    ///     let standard = morse.duration_of("PARIS PARIS");
+    ///     morse.set_weight(60.0);
+    ///     let elements = morse.timeline(".-");
+    ///     assert_eq!(elements[0].tone.duration, Duration::from_millis(72));
+    ///     assert_eq!(elements[1].tone.duration, Duration::from_millis(48));
+    ///     assert_eq!(elements[2].tone.duration, Duration::from_millis(192));
+    ///
+    ///     // Le message dure presque autant, mais la clé est enfoncée plus longtemps
+    ///     let weighted = morse.duration_of("PARIS PARIS");
+    ///     assert_eq!(weighted.duration, standard.duration + Duration::from_millis(12));
+    ///     assert!(weighted.key_down_ratio() > standard.key_down_ratio());
+    ///
+    ///     // Le décodeur retrouve le texte et la vitesse, quelle que soit la pondération
+    ///     morse.set_sampling_rate(8000);
+    ///     for weight in [30.0, 70.0] {
+    ///         morse.set_weight(weight);
+    ///         let samples: Vec<f32> = morse.text_source("HELLO WORLD").collect();
+    ///         let decoded = Morse::new().decode_samples(&samples, 8000);
+    ///         assert_eq!(decoded.text, "HELLO WORLD");
+    ///         assert!((decoded.wpm - 20.0).abs() < 1.0);
+    ///     }
+    /// }
+    /// ```
+    pub fn set_weight(&mut self, weight: f32) {
+        self.weight = weight.clamp(10.0, 90.0);
+    }
+
+    /// Retourne la pondération de la manipulation, en pourcentage.
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    /// Change la fréquence du bip court et du bip long.
+    ///
+    /// # Arguments
+    ///
+    /// * `frequency` - La fréquence en Hz.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_tone(600.0);
+    ///     let elements = morse.timeline(".-");
+    ///     assert_eq!(elements[0].tone.frequency, 600.0);
+    ///     assert_eq!(elements[2].tone.frequency, 600.0);
+    /// }
+    /// ```
+    pub fn set_tone(&mut self, frequency: f32) {
+        self.short_beep.frequency = frequency;
+        self.long_beep.frequency = frequency;
+    }
+
+    /// Ajoute un caractère à l'alphabet, ou remplace son code.
+    ///
+    /// Un caractère qui utilisait déjà ce code est retiré de l'alphabet, pour que le décodage
+    /// reste sans ambiguïté.
+    ///
+    /// # Arguments
+    ///
+    /// * `character` - Le caractère à ajouter.
+    /// * `code` - Son code Morse.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.add_character('É', "..-..");
+    ///     assert_eq!(morse.encode("É"), "..-..");
+    ///     assert_eq!(morse.decode("..-.."), "É");
+    /// }
+    /// ```
+    pub fn add_character(&mut self, character: char, code: &str) {
+        self.morse_code
+            .retain(|(c, existing)| *c != character && existing != code);
+        self.morse_code.insert((character, code.to_string()));
+    }
+
+    /// Change la politique d'écrêtage appliquée lors de l'export audio.
+    ///
+    /// Par défaut, les échantillons hors de la plage [-1.0, 1.0] sont écrêtés (`ClippingPolicy::HardClip`).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{ClippingPolicy, Morse};
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_clipping_policy(ClippingPolicy::SoftLimit);
+    /// }
+    /// ```
+    pub fn set_clipping_policy(&mut self, clipping_policy: ClippingPolicy) {
+        self.clipping_policy = clipping_policy;
+    }
+
+    /// Change la normalisation du volume appliquée lors de l'export audio.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Morse, Normalization};
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_normalization(Normalization::Peak(-1.0)); // pic à -1 dBFS
+    /// }
+    /// ```
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+    }
+
+    /// Change le nombre de bits par échantillon des fichiers WAV exportés (8, 16, 24 ou 32).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_bits_per_sample(8);
+    /// }
+    /// ```
+    pub fn set_bits_per_sample(&mut self, bits_per_sample: u16) {
+        self.bits_per_sample = bits_per_sample;
+    }
+
+    /// Active ou désactive le dither triangulaire (TPDF) lors de la conversion en entiers.
+    ///
+    /// Le dither masque les erreurs de quantification, surtout utile pour les faibles résolutions.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_bits_per_sample(8);
+    ///     morse.set_dither(true);
+    /// }
+    /// ```
+    pub fn set_dither(&mut self, dither: bool) {
+        self.dither = dither;
+    }
+
+    /// Change la méthode utilisée par le décodeur audio pour choisir les fréquences à écouter.
+    ///
+    /// Par défaut (`ToneDetection::Fixed`), le décodeur écoute les fréquences du bip court et
+    /// du bip long. Avec `ToneDetection::Automatic`, il recherche la tonalité dominante du
+    /// signal (voir `detect_tone`) et l'écoute ; avec `ToneDetection::Tracking`, il suit en plus
+    /// sa dérive lente. Si aucune tonalité n'est trouvée avec un rapport signal sur bruit d'au
+    /// moins 10 dB, les fréquences configurées sont utilisées.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Morse, ToneDetection};
+    /// use std::{f32::consts::PI, time::Duration};
+    ///
+    /// fn main() {
+    ///     // Un émetteur dont la tonalité dérive de 650 à 750 Hz pendant le message
+    ///     let mut sender = Morse::new();
+    ///     sender.set_short_beep(700.0, Duration::from_millis(100), 0.2);
+    ///     sender.set_long_beep(700.0, Duration::from_millis(300), 0.2);
+    ///     sender.set_silence(0.0, Duration::from_millis(200), 0.0);
+    ///     sender.set_duration(Duration::from_millis(100));
+    ///     let elements = sender.timeline(".-. -..- / -... ..");
+    ///     let total: f32 = elements.iter().map(|element| element.tone.duration.as_secs_f32()).sum();
+    ///     let (mut samples, mut phase) = (Vec::new(), 0.0f32);
+    ///     for element in elements {
+    ///         for _ in 0..element.tone.sample_count(8000) {
+    ///             let frequency: f32 = 650.0 + 100.0 * samples.len() as f32 / 8000.0 / total;
+    ///             phase += 2.0 * PI * frequency / 8000.0;
+    ///             samples.push(if element.is_key_down() { 0.2 * phase.sin() } else { 0.0 });
+    ///         }
+    ///     }
+    ///
+    ///     let mut receiver = Morse::new();
+    ///     receiver.set_tone_detection(ToneDetection::Tracking);
+    ///     let decoded = receiver.decode_samples(&samples, 8000);
+    ///     assert_eq!(decoded.text, "RX BI");
+    ///     // La fréquence suivie a rejoint celle de la fin du message
+    ///     assert!((decoded.tone.unwrap().frequency - 750.0).abs() < 20.0);
+    /// }
+    /// ```
+    pub fn set_tone_detection(&mut self, tone_detection: ToneDetection) {
+        self.tone_detection = tone_detection;
+    }
+
     /// Joue un code Morse.
     ///
     /// Cette fonction joue un code Morse en utilisant des bips courts pour les points ('.') et des
     /// bips longs pour les tirets ('-'). Les pauses entre les signaux sont marquées par un espace (' ').
+    /// Elle bloque le thread courant jusqu'à la fin du message ; voir `play` pour une lecture
+    /// en arrière-plan.
     ///
     /// # Arguments
     ///
@@ -453,7 +900,7 @@ impl Morse {
     ///
     /// # Exemple
     ///
-    /// ```rust
+    /// ```no_run
     /// use crate::morseus::Morse;
     ///
     /// fn main() {
@@ -461,144 +908,1332 @@ impl Morse {
     ///     morse.play_morse_code(".- / -... / -.-.");  // Joue le code Morse "A B C"
     /// }
     /// ```
+    #[cfg(feature = "playback")]
     pub fn play_morse_code(&self, morse_code: &str) {
-        for c in morse_code.chars() {
-            match c {
-                '.' => self.play_short_beep(), // Joue un bip court pour le point '.'
-                '-' => self.play_long_beep(),  // Joue un bip long pour le tiret '-'
-                _ => self.play_silence(),      // Ignorer les autres caractères
-            }
-            // Attendre une courte pause entre chaque signal
-            std::thread::sleep(self.duration);
-        }
+        let mut sink: RodioSink = RodioSink::try_default().unwrap();
+        let Ok(()) = self.play_morse_code_on(morse_code, &mut sink);
     }
 
-    /// Traduit le texte entre le code Morse et le texte normal.
+    /// Joue un code Morse sur une destination audio quelconque.
     ///
-    /// Cette méthode prend une chaîne de caractères `text` en paramètre et effectue
-    /// la traduction entre le texte normal et le code Morse. Chaque mot dans la chaîne
-    /// est analysé pour déterminer s'il est en code Morse ou en texte normal. Les mots
-    /// en code Morse sont décodés en texte normal, tandis que les mots en texte normal
-    /// sont encodés en code Morse. La traduction résultante est retournée sous forme de
-    /// chaîne de caractères.
+    /// Les éléments sont écrits un par un dans `sink`, avec leurs échantillons, puis
+    /// `AudioSink::finish` est appelée. `play_morse_code` utilise cette fonction avec un
+    /// `RodioSink` ; un `MemorySink` permet de vérifier le résultat sans matériel audio.
     ///
     /// # Arguments
     ///
-    /// * `text` - Le texte à traduire entre le code Morse et le texte normal.
+    /// * `morse_code` - Le code Morse à jouer.
+    /// * `sink` - La destination des échantillons.
     ///
     /// # Exemple
     ///
     /// ```rust
-    /// use crate::morseus::Morse;
+    /// use crate::morseus::{ElementKind, MemorySink, Morse};
+    /// use std::time::Duration;
     ///
     /// fn main() {
-    ///     let morse = Morse::new();
-    ///     let text = "HELLO WORLD ... --- ...";
-    ///     let translated = morse.translate(text);
-    ///     println!("Traduction : {}", translated);
-    ///     let morse_code = "... --- ...";
-    ///     let translated = morse.translate(morse_code);
-    ///     println!("Traduction : {}", translated);
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     let mut sink = MemorySink::new();
+    ///     let Ok(()) = morse.play_morse_code_on(".-", &mut sink);
+    ///     assert_eq!(sink.elements()[2].kind, ElementKind::Dash);
+    ///     assert_eq!(sink.duration(), Duration::from_secs_f32(2.5));
+    ///     assert_eq!(sink.samples().len(), 20000);
+    ///     assert!(sink.is_finished());
     /// }
     /// ```
-    pub fn translate(&self, text: &str) -> String {
-        let mut translated: String = String::new();
-        let words: Vec<&str> = text.split(' ').collect();
-
-        for word in words {
-            if self.is_morse(word) {
-                // Le mot est en code Morse, le traduire en texte normal
-                let decoded_word = self.decode(word);
-                translated.push_str(&decoded_word);
-            } else {
-                // Le mot est en texte normal, le traduire en code Morse
-                let encoded_word = self.encode(word);
-                translated.push_str(&encoded_word);
-            }
-            translated.push(' ');
-        }
-
-        translated.trim().to_string()
+    pub fn play_morse_code_on<S: AudioSink>(
+        &self,
+        morse_code: &str,
+        sink: &mut S,
+    ) -> Result<(), S::Error> {
+        self.write_elements(self.steps(morse_code).filter_map(Step::element), sink)
     }
 
-    /// Exporter un code Morse en audio.
+    /// Joue un texte sur une destination audio quelconque, sans passer par `encode`.
     ///
     /// # Arguments
     ///
-    /// * `morse_code` - Le code Morse à jouer.
+    /// * `text` - Le texte à jouer.
+    /// * `sink` - La destination des échantillons.
     ///
     /// # Exemple
     ///
     /// ```rust
-    /// use crate::morseus::Morse;
+    /// use crate::morseus::{MemorySink, Morse};
     ///
     /// fn main() {
     ///     let morse = Morse::new();
-    ///     let _ = morse.to_audio(".- / -... / -.-.", "output.wav");  // exporte le son dans le fichier output.wav
+    ///     let mut from_text = MemorySink::new();
+    ///     let mut from_code = MemorySink::new();
+    ///     let Ok(()) = morse.play_text_on("SOS", &mut from_text);
+    ///     let Ok(()) = morse.play_morse_code_on(&morse.encode("SOS"), &mut from_code);
+    ///     assert_eq!(from_text.elements(), from_code.elements());
     /// }
     /// ```
-    pub fn to_audio(&self, morse_code: &str, filename: &str) -> Result<(), HoundError> {
-        let mut samples: Vec<f32> = Vec::new();
-        // Créer un flux audio
-        let (_stream, _stream_handle) = OutputStream::try_default().unwrap();
-        for c in morse_code.chars() {
-            match c {
-                // Ajoute les échantillons de bip à la liste des échantillons
-                '.' => samples
-                    .extend_from_slice(&self.short_beep.clone().collect::<Vec<f32>>().as_slice()),
-                '-' => samples
-                    .extend_from_slice(&self.long_beep.clone().collect::<Vec<f32>>().as_slice()),
-                _ => samples
-                    .extend_from_slice(&self.silence.clone().collect::<Vec<f32>>().as_slice()),
-            }
-        }
-        // Exporte les échantillons en audio
-        self.export_audio(filename, &samples, self.sampling_rate)
+    pub fn play_text_on<S: AudioSink>(&self, text: &str, sink: &mut S) -> Result<(), S::Error> {
+        self.write_elements(self.text_steps(text).filter_map(Step::element), sink)
     }
 
-    /// Exporte les échantillons audio vers un fichier.
+    /// Joue des éléments quelconques sur une destination audio, par exemple ceux produits par un
+    /// manipulateur simulé (`Keyer::elements`).
     ///
     /// # Arguments
     ///
-    /// * `filename` - Le nom du fichier à créer.
-    /// * `samples` - Les échantillons audio à exporter.
-    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons audio.
+    /// * `elements` - Les éléments à jouer.
+    /// * `sink` - La destination des échantillons.
     ///
     /// # Exemple
     ///
     /// ```rust
-    /// use crate::morseus::Morse;
+    /// use crate::morseus::{MemorySink, Morse};
     ///
     /// fn main() {
     ///     let morse = Morse::new();
-    ///     let samples: Vec<f32> = vec![0.1, 0.2, 0.3];
-    ///     morse.export_audio("output.wav", &samples, 44100).unwrap();
+    ///     let mut sink = MemorySink::new();
+    ///     let Ok(()) = morse.play_morse_code_on("...", &mut sink);
+    ///     let mut replay = MemorySink::new();
+    ///     let Ok(()) = morse.play_elements_on(sink.elements(), &mut replay);
+    ///     assert_eq!(replay.samples(), sink.samples());
     /// }
     /// ```
-    pub fn export_audio(
+    pub fn play_elements_on<S: AudioSink>(
         &self,
-        filename: &str,
-        samples: &[f32],
-        sampling_rate: u32,
-    ) -> Result<(), HoundError> {
-        // Spécification du fichier WAV
-        let spec: WavSpec = WavSpec {
-            channels: 1,                             // Nombre de canaux audio (mono)
-            sample_rate : sampling_rate,                             // Taux d'échantillonnage
-            bits_per_sample: 16,                     // Nombre de bits par échantillon (16 bits)
-            sample_format: hound::SampleFormat::Int, // Format d'échantillonage (entier)
-        };
-        // Création du writer pour écrire les données audio dans le fichier
-        let mut writer: WavWriter<BufWriter<File>> = WavWriter::create(filename, spec)?;
-        // Parcours chaque échantillon dans le tableau de samples
-        for &sample in samples {
-            // Conversion de l'échantillon en i16 (format attendu par le writer)
-            let sample_i16 = (sample * std::i16::MAX as f32) as i16;
-            // Écriture de l'échantillon dans le fichier
-            writer.write_sample(sample_i16)?;
-        }
-        // Finalisation de l'écriture du fichier
-        writer.finalize()?;
-        Ok(())
+        elements: &[Element],
+        sink: &mut S,
+    ) -> Result<(), S::Error> {
+        self.write_elements(elements.iter().copied(), sink)
+    }
+
+    /// Joue un code Morse en arrière-plan.
+    ///
+    /// La lecture démarre immédiatement sur un flux audio ouvert une seule fois, et le `Player`
+    /// retourné permet de la mettre en pause, de la reprendre, de l'arrêter, de se déplacer à un
+    /// caractère et de suivre sa progression grâce aux événements `PlayerEvent`.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse_code` - Le code Morse à jouer.
+    ///
+    /// # Exemple
+    ///
+    /// ```no_run
+    /// use crate::morseus::{Morse, Player, PlayerEvent};
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let player: Player = morse.play(".- / -... / -.-.").unwrap();
+    ///     for event in player.events() {
+    ///         match event {
+    ///             PlayerEvent::CharacterStarted { character, code, .. } => {
+    ///                 println!("{:?} ({})", character, code)
+    ///             }
+    ///             PlayerEvent::WordFinished { index } => println!("fin du mot {}", index),
+    ///             PlayerEvent::Done => break,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "playback")]
+    pub fn play(&self, morse_code: &str) -> Result<Player, PlayerError> {
+        Player::new(Steps::MorseCode(self.steps(morse_code)), self.sampling_rate)
+    }
+
+    /// Joue un texte en arrière-plan, sans passer par `encode`.
+    ///
+    /// Le texte est encodé avec l'alphabet de la structure `Morse` au fur et à mesure de la
+    /// lecture. Les événements `PlayerEvent` et `Player::seek` utilisent les positions des
+    /// caractères dans le texte d'origine ; les caractères absents de l'alphabet sont ignorés.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à jouer.
+    ///
+    /// # Exemple
+    ///
+    /// ```no_run
+    /// use crate::morseus::{Morse, Player, PlayerEvent};
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let text = "SOS À L'AIDE";
+    ///     let player: Player = morse.play_text(text).unwrap();
+    ///     for event in player.events() {
+    ///         match event {
+    ///             // `index` compte les caractères, pas les octets
+    ///             PlayerEvent::CharacterStarted { index, .. } => {
+    ///                 println!("{}", text.chars().take(index + 1).collect::<String>())
+    ///             }
+    ///             PlayerEvent::WordFinished { .. } => {}
+    ///             PlayerEvent::Done => break,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "playback")]
+    pub fn play_text(&self, text: &str) -> Result<Player, PlayerError> {
+        Player::new(Steps::Text(self.text_steps(text)), self.sampling_rate)
+    }
+
+    /// Ouvre une tonalité d'écoute pour un manipulateur, à la fréquence et à l'amplification
+    /// du bip court.
+    ///
+    /// La tonalité est émise tant que la clé est enfoncée ; associée à `timing_decoder`, elle
+    /// permet de manipuler au clavier en entendant ses propres signaux.
+    ///
+    /// # Exemple
+    ///
+    /// ```no_run
+    /// use crate::morseus::{Morse, Sidetone};
+    /// use std::{thread, time::Duration};
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let sidetone: Sidetone = morse.sidetone().unwrap();
+    ///     // Un tiret de 300 ms
+    ///     sidetone.key_down();
+    ///     thread::sleep(Duration::from_millis(300));
+    ///     sidetone.key_up();
+    /// }
+    /// ```
+    #[cfg(feature = "playback")]
+    pub fn sidetone(&self) -> Result<Sidetone, PlayerError> {
+        Sidetone::new(self.short_beep, self.sampling_rate)
+    }
+
+    /// Traduit le texte entre le code Morse et le texte normal.
+    ///
+    /// Cette méthode prend une chaîne de caractères `text` en paramètre et effectue
+    /// la traduction entre le texte normal et le code Morse. Chaque mot dans la chaîne
+    /// est analysé pour déterminer s'il est en code Morse ou en texte normal. Les mots
+    /// en code Morse sont décodés en texte normal, tandis que les mots en texte normal
+    /// sont encodés en code Morse. La traduction résultante est retournée sous forme de
+    /// chaîne de caractères.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à traduire entre le code Morse et le texte normal.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let text = "HELLO WORLD ... --- ...";
+    ///     let translated = morse.translate(text);
+    ///     println!("Traduction : {}", translated);
+    ///     let morse_code = "... --- ...";
+    ///     let translated = morse.translate(morse_code);
+    ///     println!("Traduction : {}", translated);
+    /// }
+    /// ```
+    pub fn translate(&self, text: &str) -> String {
+        let mut translated: String = String::new();
+        let words: Vec<&str> = text.split(' ').collect();
+
+        for word in words {
+            if self.is_morse(word) {
+                // Le mot est en code Morse, le traduire en texte normal
+                let decoded_word = self.decode(word);
+                translated.push_str(&decoded_word);
+            } else {
+                // Le mot est en texte normal, le traduire en code Morse
+                let encoded_word = self.encode(word);
+                translated.push_str(&encoded_word);
+            }
+            translated.push(' ');
+        }
+
+        translated.trim().to_string()
+    }
+
+    /// Retourne les éléments (bips, silences et pauses) qui composent un code Morse.
+    ///
+    /// Chaque symbole du code est suivi d'une pause dont la durée est réglée par `set_duration`.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse_code` - Le code Morse à convertir.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Element, ElementKind, Morse};
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let kinds: Vec<ElementKind> = morse.timeline(".-").iter().map(|e| e.kind).collect();
+    ///     assert_eq!(
+    ///         kinds,
+    ///         vec![ElementKind::Dot, ElementKind::Pause, ElementKind::Dash, ElementKind::Pause]
+    ///     );
+    /// }
+    /// ```
+    pub fn timeline(&self, morse_code: &str) -> Vec<Element> {
+        self.steps(morse_code).filter_map(Step::element).collect()
+    }
+
+    /// Crée une source audio paresseuse pour un code Morse.
+    ///
+    /// Les échantillons sont générés à la demande, au taux d'échantillonnage de la structure
+    /// `Morse` : la mémoire utilisée ne dépend pas de la longueur du message. Avec la
+    /// fonctionnalité `playback`, la source peut être jouée directement par un `rodio::Sink`.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse_code` - Le code Morse à convertir.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Morse, MorseSource};
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     let source: MorseSource = morse.source(".-");
+    ///     // bip court (0.5 s) + pause (0.5 s) + bip long (1 s) + pause (0.5 s)
+    ///     assert_eq!(source.count(), 20000);
+    /// }
+    /// ```
+    pub fn source(&self, morse_code: &str) -> MorseSource {
+        MorseSource::new(
+            self.steps(morse_code).filter_map(Step::element),
+            self.sampling_rate,
+        )
+    }
+
+    /// Génère les échantillons audio d'un code Morse, sans les écrire nulle part.
+    ///
+    /// Les échantillons sont produits au taux d'échantillonnage de la structure `Morse`, et
+    /// chaque signal est suivi de la pause réglée par `set_duration`, comme lors de la lecture.
+    ///
+    /// Ce minutage diffère des premières versions, où les signaux étaient rendus bout à bout
+    /// sans pause : « ... » donnait alors un seul bip continu, impossible à décoder, et un
+    /// fichier exporté ne correspondait pas à ce que `play_morse_code` faisait entendre. Les
+    /// rendus sont donc plus longs qu'avant, d'une pause par signal ou séparateur.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse_code` - Le code Morse à convertir.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     let samples: Vec<f32> = morse.render_samples(".-");
+    ///     assert!(!samples.is_empty());
+    ///     assert!(samples.iter().all(|sample| sample.abs() <= 0.20));
+    ///
+    ///     // Deux points restent séparés : bip (0.5 s), pause (0.5 s), bip, pause
+    ///     morse.set_sampling_rate(8000);
+    ///     let samples: Vec<f32> = morse.render_samples("..");
+    ///     assert_eq!(samples.len(), 16000);
+    ///     assert!(samples[4000..8000].iter().all(|sample| *sample == 0.0));
+    /// }
+    /// ```
+    pub fn render_samples(&self, morse_code: &str) -> Vec<f32> {
+        self.source(morse_code).collect()
+    }
+
+    /// Crée une source audio paresseuse pour un texte, sans passer par `encode`.
+    ///
+    /// Les échantillons produits sont identiques à ceux de `source(&morse.encode(text))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à convertir.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     let from_text: Vec<f32> = morse.text_source("HI U").collect();
+    ///     let from_code: Vec<f32> = morse.source(&morse.encode("HI U")).collect();
+    ///     assert_eq!(from_text, from_code);
+    /// }
+    /// ```
+    pub fn text_source(&self, text: &str) -> MorseSource {
+        MorseSource::new(
+            self.text_steps(text).filter_map(Step::element),
+            self.sampling_rate,
+        )
+    }
+
+    /// Retourne les sous-titres d'un texte : pour chaque caractère joué, sa position dans le
+    /// texte d'origine et l'intervalle de temps pendant lequel il est joué.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à convertir.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Morse, Subtitle};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let subtitles: Vec<Subtitle> = morse.subtitles("E T");
+    ///     assert_eq!(subtitles.len(), 2);
+    ///     assert_eq!(subtitles[1].index, 2);
+    ///     assert_eq!(subtitles[1].character, Some('T'));
+    ///     // E : bip court (0.5 s) + pause (0.5 s)
+    ///     assert_eq!(subtitles[0].end, Duration::from_secs(1));
+    ///     // ' ' et '/' : trois silences (1 s) suivis chacun d'une pause (0.5 s)
+    ///     assert_eq!(subtitles[1].start, Duration::from_secs_f32(5.5));
+    /// }
+    /// ```
+    pub fn subtitles(&self, text: &str) -> Vec<Subtitle> {
+        subtitles(self.text_steps(text))
+    }
+
+    /// Calcule la durée d'un texte avec les réglages actuels (vitesse, espacement Farnsworth),
+    /// sans produire d'audio : durée totale, nombre de points, part du temps où la clé est
+    /// enfoncée, nombre de caractères et de mots, et durée de chaque caractère.
+    ///
+    /// La durée s'arrête à la fin du dernier signal. Le point vaut la moyenne d'un bip court et
+    /// de la pause qui suit chaque signal.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à mesurer.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{MessageDuration, Morse};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_wpm(20.0);
+    ///     // PARIS dure 43 points, et 50 avec l'espace qui le sépare du mot suivant
+    ///     let paris: MessageDuration = morse.duration_of("PARIS");
+    ///     assert!((paris.units - 43.0).abs() < 1e-6);
+    ///     assert_eq!(paris.duration, Duration::from_millis(43 * 60));
+    ///     assert_eq!(paris.character_count(), 5);
+    ///     assert_eq!(paris.words, 1);
+    ///     // P (.--.) : 1 + 3 + 3 + 1 points de signaux et 3 points entre eux
+    ///     assert!((paris.characters[0].units - 11.0).abs() < 1e-6);
+    ///     assert!((paris.key_down_ratio() - 22.0 / 43.0).abs() < 1e-6);
+    ///
+    ///     let message = morse.duration_of("PARIS PARIS");
+    ///     assert!((message.units - 93.0).abs() < 1e-6);
+    ///     assert_eq!(message.words, 2);
+    ///
+    ///     // Avec l'espacement Farnsworth, les caractères gardent leur durée
+    ///     morse.set_farnsworth(20.0, 10.0);
+    ///     let slower = morse.duration_of("PARIS PARIS");
+    ///     assert!(slower.duration > message.duration);
+    ///     assert_eq!(slower.characters[0].duration, paris.characters[0].duration);
+    /// }
+    /// ```
+    pub fn duration_of(&self, text: &str) -> MessageDuration {
+        message_duration(
+            self.text_steps(text),
+            (self.short_beep.duration + self.duration) / 2,
+        )
+    }
+
+    /// Crée un manipulateur simulé réglé comme cette structure : durée du point, rapport
+    /// tiret/point, pondération et tonalité du bip court.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Le comportement du manipulateur.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Keyer, KeyerMode, Morse, Paddle, PaddleEvent};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_wpm(20.0);
+    ///     morse.set_dah_ratio(4.0);
+    ///     let keyer: Keyer = morse.keyer(KeyerMode::Ultimatic);
+    ///     let elements = keyer.key(&[
+    ///         PaddleEvent::press(Paddle::Dah, Duration::ZERO),
+    ///         PaddleEvent::release(Paddle::Dah, Duration::from_millis(100)),
+    ///     ]);
+    ///     assert_eq!(elements[0].end, Duration::from_millis(240));
+    /// }
+    /// ```
+    pub fn keyer(&self, mode: KeyerMode) -> Keyer {
+        Keyer::with_timing(
+            mode,
+            (self.short_beep.duration + self.duration) / 2,
+            self.dah_ratio,
+            self.weight,
+            self.short_beep,
+        )
+    }
+
+    /// Décode un fichier audio WAV contenant du code Morse.
+    ///
+    /// Le fichier peut avoir n'importe quel taux d'échantillonnage et nombre de bits (voir
+    /// `decode_samples` pour la méthode de décodage).
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - Le nom du fichier à décoder.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     morse.to_audio(".- / -... / -.-.", "roundtrip.wav").unwrap();
+    ///     let decoded = morse.decode_audio("roundtrip.wav").unwrap();
+    ///     assert_eq!(decoded.text, "A B C");
+    ///     assert_eq!(decoded.morse_code, ".- / -... / -.-.");
+    /// #   std::fs::remove_file("roundtrip.wav").unwrap();
+    /// }
+    /// ```
+    pub fn decode_audio(&self, filename: &str) -> Result<DecodedMessage, HoundError> {
+        let (samples, sampling_rate) = read_wav(WavReader::open(filename)?)?;
+        Ok(self.decode_samples(&samples, sampling_rate))
+    }
+
+    /// Décode des échantillons audio contenant du code Morse.
+    ///
+    /// L'amplitude des fréquences du bip court et du bip long est mesurée par blocs de 10 ms
+    /// au plus (filtre de Goertzel). Un seuil placé entre le niveau du bruit et celui du signal
+    /// sépare les signaux des silences, puis leurs durées sont classées en points, tirets et
+    /// espaces par un modèle adaptatif : la vitesse est estimée sur les premiers signaux, puis
+    /// suivie tout au long du message. Un taux d'échantillonnage nul ne permet pas de dater les
+    /// échantillons : le message décodé est alors vide.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Les échantillons audio à décoder.
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     let samples: Vec<f32> = morse.text_source("SOS").collect();
+    ///     let decoded = morse.decode_samples(&samples, 8000);
+    ///     assert_eq!(decoded.text, "SOS");
+    ///     assert_eq!(decoded.dit, Duration::from_millis(500));
+    ///
+    ///     let decoded = morse.decode_samples(&samples, 0);
+    ///     assert_eq!(decoded.text, "");
+    ///     assert!(decoded.characters.is_empty());
+    /// }
+    /// ```
+    pub fn decode_samples(&self, samples: &[f32], sampling_rate: u32) -> DecodedMessage {
+        if sampling_rate == 0 {
+            return self.decode_runs(&[]);
+        }
+        let block_length: usize =
+            ((self.decoding_block().as_secs_f64() * sampling_rate as f64).round() as usize).max(1);
+        let block_duration: Duration =
+            Duration::from_secs_f64(block_length as f64 / sampling_rate as f64);
+        let detected: Option<DetectedTone> = match self.tone_detection {
+            ToneDetection::Fixed => None,
+            ToneDetection::Automatic | ToneDetection::Tracking => {
+                detect_tone(samples, sampling_rate)
+            }
+        };
+        let used: Option<DetectedTone> = detected.filter(|tone| tone.snr >= MIN_TONE_SNR);
+        let mut trackers: Vec<ToneTracker> = match used {
+            Some(tone) => vec![ToneTracker::new(
+                tone.frequency,
+                self.tone_detection == ToneDetection::Tracking,
+            )],
+            None => self
+                .beep_frequencies()
+                .into_iter()
+                .map(|frequency| ToneTracker::new(frequency, false))
+                .collect(),
+        };
+        let envelope: Vec<f32> = envelope(samples, &mut trackers, sampling_rate, block_length);
+        let mut decoded: DecodedMessage = self.decode_runs(&key_runs(&envelope, block_duration));
+        decoded.tone = match used {
+            Some(tone) => Some(DetectedTone {
+                frequency: trackers[0].frequency(),
+                ..tone
+            }),
+            None => detected,
+        };
+        decoded
+    }
+
+    /// Recherche la tonalité dominante d'un signal audio, entre 100 et 3000 Hz.
+    ///
+    /// Les spectres de blocs d'environ 100 ms sont additionnés et le pic le plus fort est
+    /// retenu ; sa fréquence est affinée par interpolation entre les bandes voisines. Le
+    /// rapport signal sur bruit compare la puissance du pic au niveau médian du spectre.
+    /// Retourne `None` si le signal ne contient aucune énergie dans cette plage.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Les échantillons audio à analyser.
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     morse.set_short_beep(700.0, Duration::from_millis(100), 0.2);
+    ///     morse.set_long_beep(700.0, Duration::from_millis(300), 0.2);
+    ///     let samples: Vec<f32> = morse.text_source("PARIS").collect();
+    ///     let tone = morse.detect_tone(&samples, 8000).unwrap();
+    ///     assert!((tone.frequency - 700.0).abs() < 2.0);
+    ///     assert!(tone.snr > 20.0);
+    /// }
+    /// ```
+    pub fn detect_tone(&self, samples: &[f32], sampling_rate: u32) -> Option<DetectedTone> {
+        detect_tone(samples, sampling_rate)
+    }
+
+    /// Crée un décodeur audio incrémental, qui reçoit les échantillons par morceaux.
+    ///
+    /// Le décodeur recherche les fréquences du bip court et du bip long, ou la tonalité
+    /// dominante du signal selon `set_tone_detection`, et part de la durée du bip court comme
+    /// estimation initiale d'un point avant de s'adapter au signal reçu.
+    ///
+    /// # Arguments
+    ///
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons qui seront reçus. S'il
+    ///   est nul, les échantillons ne peuvent pas être datés et sont ignorés.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{DecodedCharacter, Morse, StreamingAudioDecoder};
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     let samples: Vec<f32> = morse.text_source("HI YOU").collect();
+    ///     let mut decoder: StreamingAudioDecoder = morse.streaming_decoder(8000);
+    ///     let mut text = String::new();
+    ///     for chunk in samples.chunks(1000) {
+    ///         for decoded in decoder.push(chunk) {
+    ///             text.extend(decoded.character);
+    ///         }
+    ///     }
+    ///     // Chaque caractère est émis dès que le silence qui le suit dépasse 2 points
+    ///     assert_eq!(text, "HI YO");
+    ///     // Le message se termine sans silence : le dernier caractère est émis à la fin du flux
+    ///     let last: Vec<DecodedCharacter> = decoder.finish();
+    ///     assert_eq!(last[0].character, Some('U'));
+    ///
+    ///     let mut decoder: StreamingAudioDecoder = morse.streaming_decoder(0);
+    ///     assert!(decoder.push(&samples).is_empty());
+    ///     assert!(decoder.finish().is_empty());
+    /// }
+    /// ```
+    pub fn streaming_decoder(&self, sampling_rate: u32) -> StreamingAudioDecoder {
+        StreamingAudioDecoder::new(
+            self.beep_frequencies(),
+            self.tone_detection,
+            sampling_rate,
+            self.decoding_block(),
+            self.timing_tracker(),
+        )
+    }
+
+    /// Décode du code Morse transmis par une lumière clignotante, à partir de mesures de
+    /// luminosité régulières (images d'une vidéo, relevés d'une photodiode…).
+    ///
+    /// Les mesures sont binarisées avec une hystérésis entre les niveaux « éteint » et
+    /// « allumé » estimés sur l'ensemble de la séquence, puis les durées obtenues sont décodées
+    /// comme par `decode_timings`. La précision des durées est limitée à une image : un point
+    /// doit durer au moins trois images pour être décodé de manière fiable.
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - Les mesures de luminosité successives, dans n'importe quelle unité.
+    /// * `frame_rate` - Le nombre de mesures par seconde (par exemple 29.97 pour une vidéo).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     // Une lampe filmée à 30 images par seconde, avec un point de 200 ms (6 images)
+    ///     let mut morse = Morse::new();
+    ///     morse.set_short_beep(0.0, Duration::from_millis(200), 1.0);
+    ///     morse.set_long_beep(0.0, Duration::from_millis(600), 1.0);
+    ///     morse.set_silence(0.0, Duration::from_millis(400), 0.0);
+    ///     morse.set_duration(Duration::from_millis(200));
+    ///     let mut brightness: Vec<f32> = Vec::new();
+    ///     for element in morse.timeline(&morse.encode("SOS")) {
+    ///         let frames: usize = (element.tone.duration.as_secs_f32() * 30.0).round() as usize;
+    ///         for frame in 0..frames {
+    ///             // Luminosité de la pièce autour de 40, lampe à 200, et un peu de bruit
+    ///             let noise: f32 = if frame % 2 == 0 { 15.0 } else { -15.0 };
+    ///             brightness.push(if element.is_key_down() { 200.0 } else { 40.0 } + noise);
+    ///         }
+    ///     }
+    ///
+    ///     let decoded = morse.decode_brightness(&brightness, 30.0);
+    ///     assert_eq!(decoded.text, "SOS");
+    ///     assert_eq!(decoded.wpm.round(), 6.0);
+    /// }
+    /// ```
+    pub fn decode_brightness(&self, brightness: &[f32], frame_rate: f32) -> DecodedMessage {
+        self.decode_runs(&brightness_runs(brightness, frame_rate))
+    }
+
+    /// Décode toutes les stations présentes dans des échantillons audio, chacune sur sa propre
+    /// fréquence, à la manière d'un « skimmer » de concours.
+    ///
+    /// La bande de 100 à 3000 Hz est découpée en canaux par un banc de filtres (voir
+    /// `Skimmer`), et les durées de manipulation de chaque canal actif sont décodées
+    /// indépendamment, avec leur propre estimation de la vitesse. Les messages sont triés par
+    /// fréquence ; la fréquence et le rapport signal sur bruit de chaque station sont indiqués
+    /// dans `DecodedMessage::tone`. Les canaux qui ne produisent aucun caractère connu sont
+    /// ignorés.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Les échantillons audio à décoder.
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{DecodedMessage, Morse};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     // Deux stations à 600 et 900 Hz, à des vitesses différentes
+    ///     let mut first = Morse::new();
+    ///     first.set_sampling_rate(8000);
+    ///     first.set_short_beep(600.0, Duration::from_millis(80), 0.2);
+    ///     first.set_long_beep(600.0, Duration::from_millis(240), 0.2);
+    ///     first.set_silence(0.0, Duration::from_millis(160), 0.0);
+    ///     first.set_duration(Duration::from_millis(80));
+    ///     let mut second = Morse::new();
+    ///     second.set_sampling_rate(8000);
+    ///     second.set_short_beep(900.0, Duration::from_millis(60), 0.1);
+    ///     second.set_long_beep(900.0, Duration::from_millis(180), 0.1);
+    ///     second.set_silence(0.0, Duration::from_millis(120), 0.0);
+    ///     second.set_duration(Duration::from_millis(60));
+    ///     let a: Vec<f32> = first.text_source("CQ TEST F5ABC").collect();
+    ///     let b: Vec<f32> = second.text_source("DL1XYZ 599").collect();
+    ///     let mut mix: Vec<f32> = vec![0.0; a.len().max(b.len())];
+    ///     for (index, sample) in a.iter().enumerate() {
+    ///         mix[index] += sample;
+    ///     }
+    ///     for (index, sample) in b.iter().enumerate() {
+    ///         mix[index] += sample;
+    ///     }
+    ///
+    ///     let stations: Vec<DecodedMessage> = Morse::new().skim(&mix, 8000);
+    ///     assert_eq!(stations.len(), 2);
+    ///     assert_eq!(stations[0].text, "CQ TEST F5ABC");
+    ///     assert!((stations[0].tone.unwrap().frequency - 600.0).abs() < 10.0);
+    ///     assert_eq!(stations[1].text, "DL1XYZ 599");
+    ///     assert!((stations[1].tone.unwrap().frequency - 900.0).abs() < 10.0);
+    /// }
+    /// ```
+    pub fn skim(&self, samples: &[f32], sampling_rate: u32) -> Vec<DecodedMessage> {
+        channel_runs(samples, sampling_rate)
+            .into_iter()
+            .map(|(tone, runs)| {
+                let mut decoded: DecodedMessage = self.decode_runs(&runs);
+                decoded.tone = Some(tone);
+                decoded
+            })
+            .filter(|decoded| !decoded.text.is_empty())
+            .collect()
+    }
+
+    /// Crée un décodeur incrémental de plusieurs stations simultanées, qui reçoit les
+    /// échantillons par morceaux (voir `skim`).
+    ///
+    /// Le décodeur de chaque station part de la durée du bip court comme estimation initiale
+    /// d'un point avant de s'adapter à la vitesse reçue.
+    ///
+    /// # Arguments
+    ///
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons qui seront reçus.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Morse, Skimmer};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     morse.set_short_beep(700.0, Duration::from_millis(100), 0.2);
+    ///     morse.set_long_beep(700.0, Duration::from_millis(300), 0.2);
+    ///     morse.set_silence(0.0, Duration::from_millis(200), 0.0);
+    ///     morse.set_duration(Duration::from_millis(100));
+    ///     let samples: Vec<f32> = morse.text_source("TEST").collect();
+    ///
+    ///     let mut skimmer: Skimmer = morse.skimmer(8000);
+    ///     let mut text = String::new();
+    ///     for chunk in samples.chunks(800) {
+    ///         for skimmed in skimmer.push(chunk) {
+    ///             assert!((skimmed.frequency - 700.0).abs() < 10.0);
+    ///             text.extend(skimmed.character.character);
+    ///         }
+    ///     }
+    ///     for skimmed in skimmer.finish() {
+    ///         text.extend(skimmed.character.character);
+    ///     }
+    ///     assert_eq!(text, "TEST");
+    ///     assert_eq!(skimmer.stations().len(), 1);
+    /// }
+    /// ```
+    pub fn skimmer(&self, sampling_rate: u32) -> Skimmer {
+        Skimmer::new(sampling_rate, self.timing_tracker())
+    }
+
+    /// Décode une suite de durées de manipulation, telles que fournies par un manipulateur
+    /// relié à un port série, sans passer par l'audio.
+    ///
+    /// Les durées sont classées en points, tirets et espaces par le même modèle adaptatif que
+    /// `decode_samples` : la vitesse est estimée sur les premiers signaux, puis suivie tout au
+    /// long du message.
+    ///
+    /// # Arguments
+    ///
+    /// * `timings` - Les durées successives, avec `true` pour un signal (clé enfoncée) et
+    ///   `false` pour un silence.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let (dit, dah) = (Duration::from_millis(60), Duration::from_millis(180));
+    ///     let timings = [
+    ///         (true, dah), (false, dit), (true, dit), (false, dit), (true, dah), // K
+    ///         (false, 7 * dit),
+    ///         (true, dit), (false, dit), (true, dit), // I
+    ///     ];
+    ///     let decoded = morse.decode_timings(&timings);
+    ///     assert_eq!(decoded.text, "K I");
+    ///     assert_eq!(decoded.morse_code, "-.- / ..");
+    ///     assert_eq!(decoded.wpm.round(), 20.0);
+    /// }
+    /// ```
+    pub fn decode_timings(&self, timings: &[(bool, Duration)]) -> DecodedMessage {
+        self.decode_runs(timings)
+    }
+
+    /// Crée un décodeur incrémental de durées de manipulation, qui reçoit l'état de la clé au
+    /// fil de l'eau.
+    ///
+    /// Comme pour `streaming_decoder`, la durée du bip court sert d'estimation initiale d'un
+    /// point avant que le décodeur ne s'adapte à la vitesse reçue.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Morse, StreamingTimingDecoder};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_short_beep(600.0, Duration::from_millis(100), 0.2);
+    ///     morse.set_long_beep(600.0, Duration::from_millis(300), 0.2);
+    ///     let mut decoder: StreamingTimingDecoder = morse.timing_decoder();
+    ///     let ms = Duration::from_millis;
+    ///     // Instants de changement d'état de la clé pour "N" (-.)
+    ///     assert!(decoder.key_event(true, ms(0)).is_empty());
+    ///     assert!(decoder.key_event(false, ms(300)).is_empty());
+    ///     assert!(decoder.key_event(true, ms(400)).is_empty());
+    ///     assert!(decoder.key_event(false, ms(500)).is_empty());
+    ///     // Le caractère est émis dès que le silence qui le suit est assez long
+    ///     assert!(decoder.tick(ms(600)).is_empty());
+    ///     let decoded = decoder.tick(ms(800));
+    ///     assert_eq!(decoded[0].character, Some('N'));
+    ///     assert_eq!(decoded[0].start, ms(0));
+    ///     assert_eq!(decoded[0].end, ms(500));
+    /// }
+    /// ```
+    pub fn timing_decoder(&self) -> StreamingTimingDecoder {
+        StreamingTimingDecoder::new(self.timing_tracker())
+    }
+
+    /// Exporter un code Morse en audio.
+    ///
+    /// Retourne le rapport de niveau des échantillons exportés (voir `export_audio`). Le
+    /// minutage est celui de la lecture : chaque signal est suivi d'une pause (voir
+    /// `render_samples`).
+    ///
+    /// Les échantillons sont écrits au fur et à mesure de leur génération, en mémoire
+    /// constante sauf avec une normalisation (voir `write_wav_stream`), dans un fichier
+    /// temporaire `<filename>.part` renommé seulement si l'export réussit.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse_code` - Le code Morse à jouer.
+    /// * `filename` - Le nom du fichier à créer.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let _ = morse.to_audio(".- / -... / -.-.", "output.wav");  // exporte le son dans le fichier output.wav
+    /// }
+    /// ```
+    pub fn to_audio(&self, morse_code: &str, filename: &str) -> Result<ExportReport, HoundError> {
+        // Exporte les échantillons en audio au fur et à mesure de leur génération
+        self.export_stream(filename, self.source(morse_code))
+    }
+
+    /// Exporter un texte en audio, sans passer par `encode`. Les échantillons sont écrits au fur
+    /// et à mesure, comme avec `to_audio`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Le texte à exporter.
+    /// * `filename` - Le nom du fichier à créer.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let _ = morse.text_to_audio("SOS", "output.wav");  // exporte le son dans le fichier output.wav
+    /// }
+    /// ```
+    pub fn text_to_audio(&self, text: &str, filename: &str) -> Result<ExportReport, HoundError> {
+        self.export_stream(filename, self.text_source(text))
+    }
+
+    /// Exporter des éléments quelconques en audio, par exemple ceux produits par un manipulateur
+    /// simulé (`Keyer::elements`).
+    ///
+    /// # Arguments
+    ///
+    /// * `elements` - Les éléments à exporter.
+    /// * `filename` - Le nom du fichier à créer.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Element, ElementKind, Morse, Tone};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let beep = Tone::new(600.0, Duration::from_millis(60), 0.5);
+    ///     let elements = [Element::new(ElementKind::Dot, beep)];
+    ///     let _ = morse.elements_to_audio(&elements, "output.wav");  // exporte le son dans le fichier output.wav
+    /// }
+    /// ```
+    pub fn elements_to_audio(
+        &self,
+        elements: &[Element],
+        filename: &str,
+    ) -> Result<ExportReport, HoundError> {
+        self.export_stream(
+            filename,
+            MorseSource::new(elements.to_vec(), self.sampling_rate),
+        )
+    }
+
+    /// Exporte les échantillons audio vers un fichier.
+    ///
+    /// Voir `write_wav` pour le traitement appliqué aux échantillons. Les échantillons sont
+    /// vérifiés avant la création du fichier : un export refusé laisse intact un fichier
+    /// existant.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - Le nom du fichier à créer.
+    /// * `samples` - Les échantillons audio à exporter.
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons audio.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{ClippingPolicy, Morse};
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     let samples: Vec<f32> = vec![0.1, 0.2, 0.3];
+    ///     morse.export_audio("export.wav", &samples, 44100).unwrap();
+    ///     let exported: Vec<u8> = std::fs::read("export.wav").unwrap();
+    ///
+    ///     // Un échantillon hors plage fait échouer l'export sans toucher au fichier
+    ///     morse.set_clipping_policy(ClippingPolicy::Error);
+    ///     assert!(morse.export_audio("export.wav", &[0.1, 1.5], 44100).is_err());
+    ///     assert_eq!(std::fs::read("export.wav").unwrap(), exported);
+    ///     std::fs::remove_file("export.wav").unwrap();
+    /// }
+    /// ```
+    pub fn export_audio(
+        &self,
+        filename: &str,
+        samples: &[f32],
+        sampling_rate: u32,
+    ) -> Result<ExportReport, HoundError> {
+        // Les échantillons sont validés avant de toucher au fichier, pour qu'un export refusé
+        // ne laisse pas de fichier tronqué
+        let (samples, report) = self.process_samples(samples)?;
+        // Création du fichier qui recevra les données audio
+        let writer: BufWriter<File> = BufWriter::new(File::create(filename)?);
+        self.write_processed_wav(writer, samples.into_iter().map(Ok), sampling_rate)?;
+        Ok(report)
+    }
+
+    /// Écrit les échantillons audio au format WAV dans n'importe quel flux `Write + Seek`
+    /// (fichier, `Cursor<Vec<u8>>` en mémoire, etc.).
+    ///
+    /// Les échantillons sont d'abord normalisés (voir `set_normalization`), puis la politique
+    /// d'écrêtage est appliquée (voir `set_clipping_policy`) avant la conversion en entiers sur
+    /// `bits_per_sample` bits, avec dither optionnel. Le rapport retourné indique le pic et le
+    /// niveau RMS des échantillons réellement écrits.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Le flux dans lequel écrire le fichier WAV.
+    /// * `samples` - Les échantillons audio à exporter.
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons audio.
+    ///
+    /// # Erreurs
+    ///
+    /// Retourne `HoundError::Unsupported` si le nombre de bits par échantillon n'est pas 8, 16, 24
+    /// ou 32, et `HoundError::IoError` si un échantillon dépasse la plage avec `ClippingPolicy::Error`.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{ClippingPolicy, Morse, Normalization};
+    /// use std::io::Cursor;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     let mut buffer: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    ///     morse.write_wav(&mut buffer, &[0.1, 0.2, 0.3], 44100).unwrap();
+    ///     assert_eq!(&buffer.get_ref()[..4], b"RIFF");
+    ///     assert_eq!(buffer.get_ref().len(), 44 + 3 * 2); // en-tête + 3 échantillons de 16 bits
+    ///
+    ///     // Les échantillons trop forts sont refusés avec ClippingPolicy::Error
+    ///     morse.set_clipping_policy(ClippingPolicy::Error);
+    ///     let mut buffer: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    ///     assert!(morse.write_wav(&mut buffer, &[0.5, 1.5], 44100).is_err());
+    ///
+    ///     // Normalisation du pic à -6 dBFS
+    ///     morse.set_normalization(Normalization::Peak(-6.0));
+    ///     let mut buffer: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    ///     let report = morse.write_wav(&mut buffer, &[0.5, 1.5], 44100).unwrap();
+    ///     assert!((report.peak_dbfs + 6.0).abs() < 0.01);
+    ///     assert_eq!(report.clipped_samples, 0);
+    /// }
+    /// ```
+    pub fn write_wav<W: Write + Seek>(
+        &self,
+        writer: W,
+        samples: &[f32],
+        sampling_rate: u32,
+    ) -> Result<ExportReport, HoundError> {
+        let (samples, report) = self.process_samples(samples)?;
+        self.write_processed_wav(writer, samples.into_iter().map(Ok), sampling_rate)?;
+        Ok(report)
+    }
+
+    /// Écrit au format WAV des échantillons produits au fur et à mesure (par exemple une
+    /// `MorseSource`), sans les garder en mémoire.
+    ///
+    /// Le traitement est celui de `write_wav` : écrêtage, conversion sur `bits_per_sample` bits
+    /// et dither, appliqués à chaque échantillon. Seule la normalisation (`Normalization::Peak`
+    /// ou `Normalization::Rms`) ne peut pas être appliquée au fil de l'eau, car le gain dépend
+    /// de tout le signal : les échantillons sont alors rassemblés en mémoire avant l'écriture.
+    /// Avec `ClippingPolicy::Error`, l'erreur peut survenir après l'écriture des premiers
+    /// échantillons : le flux contient alors un fichier incomplet.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Le flux dans lequel écrire le fichier WAV.
+    /// * `samples` - Les échantillons audio à exporter.
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons audio.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::io::Cursor;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     morse.set_dither(true);
+    ///     let mut streamed: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    ///     let report = morse.write_wav_stream(&mut streamed, morse.source("-.-"), 8000).unwrap();
+    ///
+    ///     // Même fichier et même rapport qu'en passant par un tableau d'échantillons
+    ///     let mut buffered: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    ///     let samples: Vec<f32> = morse.render_samples("-.-");
+    ///     assert_eq!(morse.write_wav(&mut buffered, &samples, 8000).unwrap(), report);
+    ///     assert_eq!(streamed.get_ref(), buffered.get_ref());
+    /// }
+    /// ```
+    pub fn write_wav_stream<W, I>(
+        &self,
+        writer: W,
+        samples: I,
+        sampling_rate: u32,
+    ) -> Result<ExportReport, HoundError>
+    where
+        W: Write + Seek,
+        I: IntoIterator<Item = f32>,
+    {
+        if self.normalization != Normalization::None {
+            // Le gain dépend de tout le signal : les échantillons sont d'abord rassemblés
+            let samples: Vec<f32> = samples.into_iter().collect();
+            return self.write_wav(writer, &samples, sampling_rate);
+        }
+        self.check_bits_per_sample()?;
+        let clipping_policy: ClippingPolicy = self.clipping_policy;
+        let mut meter: LevelMeter = LevelMeter::default();
+        let samples = samples.into_iter().enumerate().map(|(index, sample)| {
+            let limited: f32 = limit(sample, clipping_policy, index)?;
+            meter.add(sample, limited);
+            Ok(limited)
+        });
+        self.write_processed_wav(writer, samples, sampling_rate)?;
+        Ok(meter.report(1.0))
+    }
+
+    /// Exporte des échantillons produits au fur et à mesure vers un fichier. Ils sont écrits
+    /// dans un fichier temporaire, renommé en `filename` seulement si l'export réussit.
+    fn export_stream<I>(&self, filename: &str, samples: I) -> Result<ExportReport, HoundError>
+    where
+        I: IntoIterator<Item = f32>,
+    {
+        self.check_bits_per_sample()?;
+        let partial: String = format!("{}.part", filename);
+        let result: Result<ExportReport, HoundError> = File::create(&partial)
+            .map_err(HoundError::from)
+            .and_then(|file| {
+                self.write_wav_stream(BufWriter::new(file), samples, self.sampling_rate)
+            });
+        match result {
+            Ok(report) => {
+                fs::rename(&partial, filename)?;
+                Ok(report)
+            }
+            Err(error) => {
+                let _ = fs::remove_file(&partial);
+                Err(error)
+            }
+        }
+    }
+
+    /// Vérifie le nombre de bits par échantillon, puis normalise et écrête les échantillons.
+    fn process_samples(&self, samples: &[f32]) -> Result<(Vec<f32>, ExportReport), HoundError> {
+        self.check_bits_per_sample()?;
+        process_samples(samples, self.normalization, self.clipping_policy)
+    }
+
+    /// Vérifie que le nombre de bits par échantillon est pris en charge (8, 16, 24 ou 32).
+    fn check_bits_per_sample(&self) -> Result<(), HoundError> {
+        if [8, 16, 24, 32].contains(&self.bits_per_sample) {
+            Ok(())
+        } else {
+            Err(HoundError::Unsupported)
+        }
+    }
+
+    /// Écrit au format WAV des échantillons déjà normalisés et écrêtés. La première erreur
+    /// rencontrée dans `samples` interrompt l'écriture.
+    fn write_processed_wav<W, I>(
+        &self,
+        writer: W,
+        samples: I,
+        sampling_rate: u32,
+    ) -> Result<(), HoundError>
+    where
+        W: Write + Seek,
+        I: IntoIterator<Item = Result<f32, HoundError>>,
+    {
+        // Spécification du fichier WAV
+        let spec: WavSpec = WavSpec {
+            channels: 1,                           // Nombre de canaux audio (mono)
+            sample_rate: sampling_rate,            // Taux d'échantillonnage
+            bits_per_sample: self.bits_per_sample, // Nombre de bits par échantillon
+            sample_format: SampleFormat::Int,      // Format d'échantillonage (entier)
+        };
+        // Création du writer pour écrire les données audio dans le flux
+        let mut writer: WavWriter<W> = WavWriter::new(writer, spec)?;
+        let mut rng: Option<Rng> = if self.dither { Some(Rng::new(0)) } else { None };
+        // Parcours chaque échantillon dans le tableau de samples
+        for sample in samples {
+            // Conversion de l'échantillon en entier (format attendu par le writer)
+            let sample: i32 = quantize(sample?, self.bits_per_sample, rng.as_mut());
+            // Écriture de l'échantillon dans le flux
+            match self.bits_per_sample {
+                8 => writer.write_sample(sample as i8)?,
+                16 => writer.write_sample(sample as i16)?,
+                _ => writer.write_sample(sample)?,
+            }
+        }
+        // Finalisation de l'écriture du fichier
+        writer.finalize()
+    }
+
+    /// Retourne les sons utilisés pour convertir le code Morse en éléments.
+    fn tones(&self) -> Tones {
+        // La pondération allonge chaque signal et raccourcit d'autant le silence qui le suit
+        let dit: Duration = (self.short_beep.duration + self.duration) / 2;
+        let adjustment: f32 = (self.weight - 50.0) / 50.0;
+        let weighted = |tone: Tone, sign: f32| -> Tone {
+            let shift: Duration = scaled(dit, adjustment.abs());
+            let duration: Duration = if adjustment * sign >= 0.0 {
+                tone.duration + shift
+            } else {
+                tone.duration.saturating_sub(shift)
+            };
+            Tone { duration, ..tone }
+        };
+        let pause: Tone = Tone::silence(self.duration);
+        Tones {
+            short_beep: weighted(self.short_beep, 1.0),
+            long_beep: weighted(self.long_beep, 1.0),
+            // Entre deux caractères, les deux pauses raccourcies sont en partie compensées
+            silence: weighted(self.silence, 1.0),
+            word_silence: weighted(self.word_silence, 1.0),
+            pause: weighted(pause, -1.0).duration,
+        }
+    }
+
+    /// Décode une suite de durées de manipulation (`true` pour un signal, `false` pour un silence).
+    fn decode_runs(&self, runs: &[(bool, Duration)]) -> DecodedMessage {
+        let (characters, dit) = decode_runs(self.characters(), self.gaps(), runs);
+        let morse_code: String = characters_to_morse(&characters);
+        DecodedMessage {
+            text: self.decode(&morse_code),
+            morse_code,
+            dit,
+            wpm: dit_to_wpm(dit),
+            characters,
+            tone: None,
+        }
+    }
+
+    /// Écrit les éléments et leurs échantillons dans une destination audio.
+    fn write_elements<I, S>(&self, elements: I, sink: &mut S) -> Result<(), S::Error>
+    where
+        I: Iterator<Item = Element>,
+        S: AudioSink,
+    {
+        for element in elements {
+            let samples: Vec<f32> = MorseSource::new([element], self.sampling_rate).collect();
+            sink.write(&element, &samples, self.sampling_rate)?;
+        }
+        sink.finish()
+    }
+
+    /// Retourne les étapes (éléments et repères) qui composent un code Morse.
+    fn steps(&self, morse_code: &str) -> MorseCodeSteps {
+        MorseCodeSteps::new(morse_code.to_string(), self.characters(), self.tones())
+    }
+
+    /// Retourne la table qui associe chaque code Morse à son caractère.
+    fn characters(&self) -> HashMap<String, char> {
+        self.morse_code
+            .iter()
+            .map(|(c, code)| (code.clone(), *c))
+            .collect()
+    }
+
+    /// Retourne les fréquences (non nulles) du bip court et du bip long.
+    fn beep_frequencies(&self) -> Vec<f32> {
+        [self.short_beep.frequency, self.long_beep.frequency]
+            .into_iter()
+            .filter(|frequency| *frequency > 0.0)
+            .collect()
+    }
+
+    /// Durée des blocs d'analyse audio : assez courts pour découper le plus court des signaux
+    /// en quatre, et au plus 10 ms.
+    fn decoding_block(&self) -> Duration {
+        let shortest: Duration = self
+            .short_beep
+            .duration
+            .min(self.long_beep.duration)
+            .min(self.duration);
+        (shortest / 4).min(Duration::from_millis(10))
+    }
+
+    /// Crée un décodeur de durées de manipulation qui attend les durées des bips configurés.
+    fn timing_tracker(&self) -> TimingTracker {
+        let mut tracker: TimingTracker = TimingTracker::new(
+            self.characters(),
+            self.short_beep.duration,
+            self.long_beep.duration,
+        );
+        let (character_gap, word_gap) = self.gaps();
+        tracker.set_gaps(character_gap, word_gap);
+        tracker
+    }
+
+    /// Retourne les durées des silences entre les caractères et entre les mots, en points.
+    ///
+    /// Entre deux caractères, le dernier signal est suivi d'une pause, d'un espace et d'une
+    /// pause ; entre deux mots, de trois pauses, de deux espaces et du séparateur '/'. Ces
+    /// durées ne sont retenues que si la pause entre les signaux dure un point (minutage réglé
+    /// par `set_wpm` ou `set_farnsworth`) ; sinon les silences standards (3 et 7 points) sont
+    /// attendus.
+    fn gaps(&self) -> (f64, f64) {
+        let dit: f64 = self.short_beep.duration.as_secs_f64();
+        let pause: f64 = self.duration.as_secs_f64();
+        if dit <= 0.0 || (pause / dit - 1.0).abs() > 0.1 {
+            return (3.0, 7.0);
+        }
+        let character_gap: f64 = 2.0 * pause + self.silence.duration.as_secs_f64();
+        let word_gap: f64 = 2.0 * character_gap + self.word_silence.duration.as_secs_f64();
+        (character_gap / dit, word_gap / dit)
+    }
+
+    /// Retourne les étapes (éléments et repères) qui composent un texte.
+    fn text_steps(&self, text: &str) -> TextSteps {
+        let codes: HashMap<char, String> = self.morse_code.iter().cloned().collect();
+        TextSteps::new(text.to_string(), codes, self.tones())
+    }
+
+    /// Crée une source audio pour un seul élément.
+    #[cfg(feature = "playback")]
+    fn source_of(&self, element: Element) -> MorseSource {
+        MorseSource::new([element], self.sampling_rate)
     }
 }
@@ -0,0 +1,245 @@
+use rodio::{OutputStream, OutputStreamHandle, PlayError, Sink, StreamError};
+
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
+};
+
+use crate::{
+    source::MorseSource,
+    timeline::{skip_to_character, Element, Marker, Step, Steps},
+};
+
+/// Événement émis pendant la lecture d'un message par un `Player`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayerEvent {
+    /// Un caractère commence à être joué.
+    CharacterStarted {
+        /// Position du caractère dans le message : dans le texte d'origine pour un texte,
+        /// parmi les caractères Morse pour un code Morse.
+        index: usize,
+        /// Le caractère, s'il est connu de l'alphabet.
+        character: Option<char>,
+        /// Le code Morse du caractère.
+        code: String,
+    },
+    /// Un mot vient d'être entièrement joué.
+    WordFinished {
+        /// Position du mot dans le message.
+        index: usize,
+    },
+    /// Le message a été entièrement joué.
+    Done,
+}
+
+impl From<Marker> for PlayerEvent {
+    fn from(marker: Marker) -> Self {
+        match marker {
+            Marker::CharacterStarted {
+                index,
+                character,
+                code,
+            } => PlayerEvent::CharacterStarted {
+                index,
+                character,
+                code,
+            },
+            Marker::WordFinished { index } => PlayerEvent::WordFinished { index },
+        }
+    }
+}
+
+/// Erreur survenue lors de l'ouverture du dispositif audio.
+#[derive(Debug)]
+pub enum PlayerError {
+    /// Le flux de sortie audio n'a pas pu être ouvert.
+    Stream(StreamError),
+    /// La lecture n'a pas pu être démarrée sur le flux de sortie.
+    Play(PlayError),
+}
+
+impl fmt::Display for PlayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayerError::Stream(error) => {
+                write!(f, "impossible d'ouvrir la sortie audio : {}", error)
+            }
+            PlayerError::Play(error) => write!(f, "impossible de démarrer la lecture : {}", error),
+        }
+    }
+}
+
+impl Error for PlayerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlayerError::Stream(error) => Some(error),
+            PlayerError::Play(error) => Some(error),
+        }
+    }
+}
+
+impl From<StreamError> for PlayerError {
+    fn from(error: StreamError) -> Self {
+        PlayerError::Stream(error)
+    }
+}
+
+impl From<PlayError> for PlayerError {
+    fn from(error: PlayError) -> Self {
+        PlayerError::Play(error)
+    }
+}
+
+/// Lecture d'un message Morse en arrière-plan sur le dispositif audio par défaut.
+///
+/// Le flux de sortie est ouvert une seule fois pour tout le message. La lecture peut être mise
+/// en pause, reprise, arrêtée ou déplacée à un caractère donné, et les événements de lecture
+/// (début de caractère, fin de mot, fin du message) sont envoyés sur un canal accessible avec
+/// `events`. Le flux audio doit rester sur le thread qui l'a ouvert : un `Player` ne peut pas
+/// être envoyé à un autre thread.
+pub struct Player {
+    _stream: OutputStream,
+    stream_handle: OutputStreamHandle,
+    sink: Sink,
+    steps: Steps,
+    sampling_rate: u32,
+    sender: Sender<PlayerEvent>,
+    receiver: Receiver<PlayerEvent>,
+    current_character: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Player {
+    /// Ouvre le dispositif audio par défaut et démarre la lecture des étapes.
+    pub(crate) fn new(steps: Steps, sampling_rate: u32) -> Result<Self, PlayerError> {
+        let (stream, stream_handle) = OutputStream::try_default()?;
+        let sink: Sink = Sink::try_new(&stream_handle)?;
+        let (sender, receiver) = channel();
+        let mut player: Player = Player {
+            _stream: stream,
+            stream_handle,
+            sink,
+            steps,
+            sampling_rate,
+            sender,
+            receiver,
+            current_character: Arc::new(AtomicUsize::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        player.start(0);
+        Ok(player)
+    }
+
+    /// Met la lecture en pause.
+    pub fn pause(&self) {
+        self.sink.pause();
+    }
+
+    /// Reprend la lecture après une pause.
+    pub fn resume(&self) {
+        self.sink.play();
+    }
+
+    /// Indique si la lecture est en pause.
+    pub fn is_paused(&self) -> bool {
+        self.sink.is_paused()
+    }
+
+    /// Arrête définitivement la lecture. Aucun événement `Done` n'est émis.
+    pub fn stop(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.sink.stop();
+    }
+
+    /// Reprend la lecture à partir du caractère à la position `index` du message.
+    ///
+    /// Pour un message lu avec `Morse::play_text`, `index` est la position du caractère dans le
+    /// texte d'origine.
+    ///
+    /// L'état de pause est conservé.
+    pub fn seek(&mut self, index: usize) -> Result<(), PlayerError> {
+        let paused: bool = self.sink.is_paused();
+        self.stop();
+        self.sink = Sink::try_new(&self.stream_handle)?;
+        if paused {
+            self.sink.pause();
+        }
+        self.start(index);
+        Ok(())
+    }
+
+    /// Indique si la lecture est terminée (ou arrêtée).
+    pub fn is_done(&self) -> bool {
+        self.sink.empty()
+    }
+
+    /// Bloque le thread courant jusqu'à la fin de la lecture.
+    pub fn wait(&self) {
+        self.sink.sleep_until_end();
+    }
+
+    /// Retourne la position dans le message du dernier caractère commencé.
+    pub fn current_character(&self) -> usize {
+        self.current_character.load(Ordering::SeqCst)
+    }
+
+    /// Retourne le canal sur lequel les événements de lecture sont envoyés.
+    pub fn events(&self) -> &Receiver<PlayerEvent> {
+        &self.receiver
+    }
+
+    /// Démarre la lecture des étapes à partir du caractère à la position `index`.
+    fn start(&mut self, index: usize) {
+        self.cancelled = Arc::new(AtomicBool::new(false));
+        self.current_character.store(index, Ordering::SeqCst);
+        let elements: EventElements<_> = EventElements {
+            steps: skip_to_character(self.steps.clone(), index),
+            sender: self.sender.clone(),
+            current_character: self.current_character.clone(),
+            cancelled: self.cancelled.clone(),
+            done: false,
+        };
+        self.sink
+            .append(MorseSource::new(elements, self.sampling_rate));
+    }
+}
+
+/// Adaptateur qui transmet les repères d'une séquence sous forme d'événements et ne laisse
+/// passer que les éléments à jouer.
+struct EventElements<I> {
+    steps: I,
+    sender: Sender<PlayerEvent>,
+    current_character: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    done: bool,
+}
+
+impl<I: Iterator<Item = Step>> Iterator for EventElements<I> {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        if self.done || self.cancelled.load(Ordering::SeqCst) {
+            return None;
+        }
+        for step in self.steps.by_ref() {
+            match step {
+                Step::Element(element) => return Some(element),
+                Step::Marker(marker) => {
+                    if let Marker::CharacterStarted { index, .. } = marker {
+                        self.current_character.store(index, Ordering::SeqCst);
+                    }
+                    // Le récepteur peut avoir été abandonné : les événements sont alors ignorés
+                    let _ = self.sender.send(marker.into());
+                }
+            }
+        }
+        self.done = true;
+        let _ = self.sender.send(PlayerEvent::Done);
+        None
+    }
+}
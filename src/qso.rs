@@ -0,0 +1,510 @@
+use crate::{morse::Morse, rng::Rng};
+
+/// Région d'indicatifs : préfixes UIT, chiffres de district, zone CQ, prénoms et villes.
+struct Region {
+    prefixes: &'static [&'static str],
+    digits: &'static str,
+    zone: u8,
+    names: &'static [&'static str],
+    /// Villes, avec leur section ARRL (« DX » hors d'Amérique du Nord).
+    places: &'static [(&'static str, &'static str)],
+}
+
+const AMERICAN_NAMES: &[&str] = &[
+    "JOHN", "BOB", "MIKE", "DAVE", "JIM", "TOM", "BILL", "STEVE", "MARY", "SUE",
+];
+
+const AMERICAN_PREFIXES: &[&str] = &["K", "W", "N", "AA", "AB", "KB", "KC", "WA", "WB"];
+
+/// Nombre de régions nord-américaines, placées en tête de `REGIONS`.
+const NORTH_AMERICA: usize = 4;
+
+const REGIONS: &[Region] = &[
+    Region {
+        prefixes: AMERICAN_PREFIXES,
+        digits: "1234",
+        zone: 5,
+        names: AMERICAN_NAMES,
+        places: &[
+            ("BOSTON", "EMA"),
+            ("HARTFORD", "CT"),
+            ("NEW YORK", "NLI"),
+            ("PHILADELPHIA", "EPA"),
+            ("ATLANTA", "GA"),
+            ("MIAMI", "SFL"),
+        ],
+    },
+    Region {
+        prefixes: AMERICAN_PREFIXES,
+        digits: "5890",
+        zone: 4,
+        names: AMERICAN_NAMES,
+        places: &[
+            ("DALLAS", "NTX"),
+            ("CHICAGO", "IL"),
+            ("DENVER", "CO"),
+            ("ST LOUIS", "MO"),
+            ("MINNEAPOLIS", "MN"),
+        ],
+    },
+    Region {
+        prefixes: AMERICAN_PREFIXES,
+        digits: "67",
+        zone: 3,
+        names: AMERICAN_NAMES,
+        places: &[
+            ("LOS ANGELES", "LAX"),
+            ("SAN DIEGO", "SDG"),
+            ("SEATTLE", "WWA"),
+            ("PHOENIX", "AZ"),
+            ("PORTLAND", "OR"),
+        ],
+    },
+    Region {
+        prefixes: &["VE", "VA"],
+        digits: "3",
+        zone: 4,
+        names: &["PAUL", "RON", "GARY", "DOUG", "LINDA"],
+        places: &[("TORONTO", "GTA"), ("OTTAWA", "ONE"), ("HAMILTON", "ONS")],
+    },
+    Region {
+        prefixes: &["F"],
+        digits: "1234568",
+        zone: 14,
+        names: &["PIERRE", "JEAN", "MICHEL", "ALAIN", "MARIE", "ERIC"],
+        places: &[
+            ("PARIS", "DX"),
+            ("LYON", "DX"),
+            ("TOULOUSE", "DX"),
+            ("NANTES", "DX"),
+            ("LILLE", "DX"),
+        ],
+    },
+    Region {
+        prefixes: &["G", "M"],
+        digits: "0345",
+        zone: 14,
+        names: &["DAVID", "PETER", "IAN", "CHRIS", "NIGEL"],
+        places: &[
+            ("LONDON", "DX"),
+            ("BRISTOL", "DX"),
+            ("LEEDS", "DX"),
+            ("YORK", "DX"),
+        ],
+    },
+    Region {
+        prefixes: &["DL", "DK", "DJ", "DF"],
+        digits: "123456789",
+        zone: 14,
+        names: &["HANS", "KLAUS", "PETER", "UWE", "WOLFGANG"],
+        places: &[
+            ("BERLIN", "DX"),
+            ("HAMBURG", "DX"),
+            ("MUENCHEN", "DX"),
+            ("KOELN", "DX"),
+        ],
+    },
+    Region {
+        prefixes: &["I", "IK", "IZ"],
+        digits: "0123456789",
+        zone: 15,
+        names: &["MARCO", "GIOVANNI", "LUCA", "PAOLO"],
+        places: &[
+            ("ROMA", "DX"),
+            ("MILANO", "DX"),
+            ("TORINO", "DX"),
+            ("NAPOLI", "DX"),
+        ],
+    },
+    Region {
+        prefixes: &["EA", "EB"],
+        digits: "1234567",
+        zone: 14,
+        names: &["JOSE", "ANTONIO", "CARLOS", "JUAN"],
+        places: &[
+            ("MADRID", "DX"),
+            ("BARCELONA", "DX"),
+            ("SEVILLA", "DX"),
+            ("VALENCIA", "DX"),
+        ],
+    },
+    Region {
+        prefixes: &["HB"],
+        digits: "9",
+        zone: 14,
+        names: &["URS", "HANS", "MARKUS", "BEAT"],
+        places: &[("ZURICH", "DX"), ("BERN", "DX"), ("GENEVE", "DX")],
+    },
+    Region {
+        prefixes: &["OH"],
+        digits: "123456789",
+        zone: 15,
+        names: &["JARI", "PEKKA", "MATTI", "KARI"],
+        places: &[("HELSINKI", "DX"), ("TAMPERE", "DX"), ("OULU", "DX")],
+    },
+    Region {
+        prefixes: &["JA", "JH", "JR", "JE"],
+        digits: "0123456789",
+        zone: 25,
+        names: &["HIRO", "TAKA", "KEN", "YOSHI", "MASA"],
+        places: &[
+            ("TOKYO", "DX"),
+            ("OSAKA", "DX"),
+            ("KYOTO", "DX"),
+            ("NAGOYA", "DX"),
+        ],
+    },
+    Region {
+        prefixes: &["VK"],
+        digits: "12345",
+        zone: 30,
+        names: &["BRUCE", "IAN", "GRAHAM", "PETER"],
+        places: &[
+            ("SYDNEY", "DX"),
+            ("MELBOURNE", "DX"),
+            ("BRISBANE", "DX"),
+            ("ADELAIDE", "DX"),
+        ],
+    },
+    Region {
+        prefixes: &["PY", "PU"],
+        digits: "12345",
+        zone: 11,
+        names: &["JOAO", "CARLOS", "PAULO", "MARCOS"],
+        places: &[("SAO PAULO", "DX"), ("RIO", "DX"), ("CURITIBA", "DX")],
+    },
+];
+
+const RIGS: &[&str] = &["IC7300", "K3", "FT991", "TS590", "KX2", "FT817", "HOMEBREW"];
+const ANTENNAS: &[&str] = &["DIPOLE", "YAGI", "VERTICAL", "EFHW", "LOOP", "G5RV"];
+const POWERS: &[&str] = &["5W", "10W", "50W", "100W", "500W"];
+const WEATHER: &[&str] = &["SUNNY", "CLOUDY", "RAIN", "SNOW", "FOGGY", "WINDY"];
+const GREETINGS: &[&str] = &["GM", "GA", "GE"];
+
+/// Concours dont les échanges peuvent être générés par `QsoGenerator::contest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contest {
+    /// CQ World Wide DX : report et zone CQ.
+    CqWorldWide,
+    /// ARRL Sweepstakes : numéro de série, catégorie, indicatif, année de licence et section.
+    ArrlSweepstakes,
+    /// ARRL Field Day : classe (nombre d'émetteurs et catégorie) et section.
+    FieldDay,
+}
+
+/// Station fictive générée par `QsoGenerator::station`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Station {
+    /// Indicatif, formé d'un préfixe UIT, d'un chiffre et d'un suffixe.
+    pub callsign: String,
+    /// Prénom de l'opérateur.
+    pub name: String,
+    /// Ville de la station.
+    pub qth: String,
+    /// Section ARRL, « DX » hors d'Amérique du Nord.
+    pub section: String,
+    /// Zone CQ.
+    pub zone: u8,
+}
+
+/// Contact généré : les transmissions successives des deux stations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Qso {
+    /// Station qui lance l'appel.
+    pub calling: Station,
+    /// Station qui répond.
+    pub answering: Station,
+    /// Transmissions, dans l'ordre, en commençant par l'appel.
+    pub transmissions: Vec<String>,
+}
+
+impl Qso {
+    /// Retourne le texte du contact, les transmissions étant séparées par des espaces.
+    pub fn text(&self) -> String {
+        self.transmissions.join(" ")
+    }
+
+    /// Retourne le code Morse du contact.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse` - L'alphabet utilisé pour encoder le contact.
+    pub fn morse_code(&self, morse: &Morse) -> String {
+        morse.encode(&self.text())
+    }
+}
+
+/// Générateur de textes d'entraînement réalistes : indicatifs, reports RST, contacts
+/// « rag-chew » et échanges de concours.
+///
+/// Les textes utilisent les abréviations et les signes de procédure usuels : '=' pour BT,
+/// '+' pour AR, et les autres signes entre chevrons (`<KN>`, `<SK>`), que
+/// `Morse::encode` transmet sans espace entre leurs lettres. Le tirage dépend uniquement de la
+/// graine, pour que les textes soient reproductibles.
+#[derive(Debug, Clone)]
+pub struct QsoGenerator {
+    rng: Rng,
+}
+
+impl QsoGenerator {
+    /// Crée un générateur à partir d'une graine.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - La graine du tirage ; la même graine donne les mêmes textes.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::QsoGenerator;
+    ///
+    /// fn main() {
+    ///     let mut generator = QsoGenerator::new(7);
+    ///     let callsign: String = generator.callsign();
+    ///     assert_eq!(QsoGenerator::new(7).callsign(), callsign);
+    ///     assert!(callsign.chars().any(|c| c.is_ascii_digit()));
+    ///     assert!(callsign.chars().last().unwrap().is_ascii_uppercase());
+    /// }
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        QsoGenerator {
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Génère un indicatif : un préfixe UIT, un chiffre de district et un suffixe de deux ou
+    /// trois lettres (jamais commençant par Q, réservé aux codes Q).
+    pub fn callsign(&mut self) -> String {
+        let region: &Region = self.region(REGIONS);
+        self.callsign_in(region)
+    }
+
+    /// Génère un report RST réaliste, par exemple « 579 ».
+    pub fn rst(&mut self) -> String {
+        let readability: u64 = if self.below(5) == 0 { 4 } else { 5 };
+        let strength: u64 = 4 + self.below(6);
+        format!("{}{}9", readability, strength)
+    }
+
+    /// Génère une station : indicatif, prénom, ville, section et zone cohérents entre eux.
+    pub fn station(&mut self) -> Station {
+        let region: &Region = self.region(REGIONS);
+        self.station_in(region)
+    }
+
+    /// Génère un contact « rag-chew » complet : appel général, réponse, échange des reports,
+    /// prénoms, villes, équipements et météo, puis salutations et fin de contact.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Morse, Qso, QsoGenerator};
+    ///
+    /// fn main() {
+    ///     let qso: Qso = QsoGenerator::new(2024).rag_chew();
+    ///     let text: String = qso.text();
+    ///     assert!(text.starts_with(&format!("CQ CQ CQ DE {}", qso.calling.callsign)));
+    ///     assert!(text.contains(&format!("NAME {}", qso.answering.name)));
+    ///     assert!(text.ends_with("<SK>"));
+    ///
+    ///     // Le texte peut être encodé tel quel ; <KN> a le même code que '('
+    ///     let morse = Morse::new();
+    ///     assert_eq!(morse.decode(&qso.morse_code(&morse)), text.replace("<KN>", "("));
+    /// }
+    /// ```
+    pub fn rag_chew(&mut self) -> Qso {
+        let calling: Station = self.station();
+        let mut answering: Station = calling.clone();
+        while answering.callsign == calling.callsign {
+            answering = self.station();
+        }
+        let (a, b) = (&calling.callsign, &answering.callsign);
+        let greeting: &str = self.pick(GREETINGS);
+        let mut transmissions: Vec<String> = vec![
+            format!("CQ CQ CQ DE {} {} K", a, a),
+            format!("{} DE {} {} K", a, b, b),
+        ];
+
+        let rst: String = self.rst();
+        transmissions.push(format!(
+            "{} DE {} = {} OM TNX FER CALL = UR RST {} {} = NAME {} {} = QTH {} {} = HW? + {} DE {} <KN>",
+            b, a, greeting, rst, rst, calling.name, calling.name, calling.qth, calling.qth, b, a
+        ));
+
+        let rst: String = self.rst();
+        let rig: &str = self.pick(RIGS);
+        let antenna: &str = self.pick(ANTENNAS);
+        let weather: &str = self.pick(WEATHER);
+        let temperature: u64 = self.below(31);
+        transmissions.push(format!(
+            "{} DE {} = R {} {} TNX FER RPT = UR RST {} {} = NAME {} {} = QTH {} {} = \
+             RIG {} ES ANT {} = WX {} TEMP {}C = HW? + {} DE {} <KN>",
+            a,
+            b,
+            greeting,
+            calling.name,
+            rst,
+            rst,
+            answering.name,
+            answering.name,
+            answering.qth,
+            answering.qth,
+            rig,
+            antenna,
+            weather,
+            temperature,
+            a,
+            b
+        ));
+
+        let rig: &str = self.pick(RIGS);
+        let power: &str = self.pick(POWERS);
+        let antenna: &str = self.pick(ANTENNAS);
+        transmissions.push(format!(
+            "{} DE {} = R FB {} = RIG {} PWR {} ANT {} = TNX FER QSO HPE CUAGN = 73 + {} DE {} <KN>",
+            b, a, answering.name, rig, power, antenna, b, a
+        ));
+        transmissions.push(format!(
+            "{} DE {} = TNX {} FER NICE QSO = 73 ES GL + {} DE {} <SK>",
+            a, b, calling.name, a, b
+        ));
+        Qso {
+            calling,
+            answering,
+            transmissions,
+        }
+    }
+
+    /// Génère un échange de concours : appel, réponse, échanges et confirmation. Les reports
+    /// utilisent les chiffres abrégés (« 5NN » pour 599). Les stations de l'ARRL Sweepstakes et
+    /// du Field Day sont nord-américaines.
+    ///
+    /// # Arguments
+    ///
+    /// * `contest` - Le concours dont l'échange est généré.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Contest, QsoGenerator};
+    ///
+    /// fn main() {
+    ///     let mut generator = QsoGenerator::new(3);
+    ///     let qso = generator.contest(Contest::CqWorldWide);
+    ///     assert_eq!(qso.transmissions[0], format!("CQ TEST {}", qso.calling.callsign));
+    ///     assert_eq!(qso.transmissions[1], qso.answering.callsign);
+    ///     assert!(qso.transmissions[2].ends_with(&format!("5NN {}", qso.calling.zone)));
+    ///
+    ///     let qso = generator.contest(Contest::FieldDay);
+    ///     assert!(qso.transmissions[2].ends_with(&qso.calling.section));
+    ///     assert_ne!(qso.calling.section, "DX");
+    /// }
+    /// ```
+    pub fn contest(&mut self, contest: Contest) -> Qso {
+        let regions: &'static [Region] = match contest {
+            Contest::CqWorldWide => REGIONS,
+            Contest::ArrlSweepstakes | Contest::FieldDay => &REGIONS[..NORTH_AMERICA],
+        };
+        let region: &Region = self.region(regions);
+        let calling: Station = self.station_in(region);
+        let mut answering: Station = calling.clone();
+        while answering.callsign == calling.callsign {
+            let region: &Region = self.region(regions);
+            answering = self.station_in(region);
+        }
+        let (a, b) = (&calling.callsign, &answering.callsign);
+        let (name, calling_exchange, answering_exchange) = match contest {
+            Contest::CqWorldWide => (
+                "TEST",
+                format!("5NN {}", calling.zone),
+                format!("5NN {}", answering.zone),
+            ),
+            Contest::ArrlSweepstakes => (
+                "SS",
+                self.sweepstakes_exchange(&calling),
+                self.sweepstakes_exchange(&answering),
+            ),
+            Contest::FieldDay => (
+                "FD",
+                format!("{} {}", self.field_day_class(), calling.section),
+                format!("{} {}", self.field_day_class(), answering.section),
+            ),
+        };
+        let transmissions: Vec<String> = vec![
+            format!("CQ {} {}", name, a),
+            b.clone(),
+            format!("{} {}", b, calling_exchange),
+            format!("R {}", answering_exchange),
+            format!("TU {} {}", a, name),
+        ];
+        Qso {
+            calling,
+            answering,
+            transmissions,
+        }
+    }
+
+    /// Échange de l'ARRL Sweepstakes : numéro de série, catégorie, indicatif, deux derniers
+    /// chiffres de l'année de licence et section.
+    fn sweepstakes_exchange(&mut self, station: &Station) -> String {
+        let serial: u64 = 1 + self.below(999);
+        let precedence: &str = self.pick(&["Q", "A", "B", "U", "M", "S"]);
+        let check: u64 = (50 + self.below(76)) % 100;
+        format!(
+            "{} {} {} {:02} {}",
+            serial, precedence, station.callsign, check, station.section
+        )
+    }
+
+    /// Classe du Field Day : nombre d'émetteurs et catégorie, par exemple « 2A ».
+    fn field_day_class(&mut self) -> String {
+        let transmitters: u64 = 1 + self.below(4);
+        let category: &str = self.pick(&["A", "B", "C", "D", "E", "F"]);
+        format!("{}{}", transmitters, category)
+    }
+
+    fn station_in(&mut self, region: &Region) -> Station {
+        let callsign: String = self.callsign_in(region);
+        let name: &str = self.pick(region.names);
+        let (qth, section) = self.pick(region.places);
+        Station {
+            callsign,
+            name: name.to_string(),
+            qth: qth.to_string(),
+            section: section.to_string(),
+            zone: region.zone,
+        }
+    }
+
+    fn callsign_in(&mut self, region: &Region) -> String {
+        let prefix: &str = self.pick(region.prefixes);
+        let digits: Vec<char> = region.digits.chars().collect();
+        let digit: char = self.pick(&digits);
+        let length: u64 = if self.below(5) < 2 { 2 } else { 3 };
+        let mut callsign: String = format!("{}{}", prefix, digit);
+        for index in 0..length {
+            let letters: &[u8] = if index == 0 {
+                b"ABCDEFGHIJKLMNOPRSTUVWXYZ"
+            } else {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZ"
+            };
+            callsign.push(self.pick(letters) as char);
+        }
+        callsign
+    }
+
+    /// Tire une région d'une liste non vide.
+    fn region(&mut self, regions: &'static [Region]) -> &'static Region {
+        &regions[self.below(regions.len() as u64) as usize]
+    }
+
+    /// Tire un entier dans l'intervalle [0, count[.
+    fn below(&mut self, count: u64) -> u64 {
+        self.rng.next_u64() % count
+    }
+
+    /// Tire un élément d'une liste non vide.
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
@@ -0,0 +1,34 @@
+/// Petit générateur pseudo-aléatoire (xorshift64*) déterministe.
+///
+/// Utilisé en interne lorsque le résultat doit être reproductible à partir d'une graine,
+/// sans dépendre d'une bibliothèque externe.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Crée un générateur à partir d'une graine. Une graine nulle est remplacée par une constante.
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+
+    /// Retourne le prochain entier pseudo-aléatoire sur 64 bits.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Retourne un flottant pseudo-aléatoire dans l'intervalle [0.0, 1.0[.
+    pub(crate) fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Opération qui transforme un caractère envoyé en caractère recopié.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// Le caractère a été recopié correctement.
+    Match(char),
+    /// Le caractère a été recopié comme un autre.
+    Substitution {
+        /// Caractère envoyé.
+        sent: char,
+        /// Caractère recopié.
+        copied: char,
+    },
+    /// Le caractère envoyé n'a pas été recopié.
+    Missed(char),
+    /// Un caractère recopié ne correspond à aucun caractère envoyé.
+    Extra(char),
+}
+
+/// Résultats d'un caractère envoyé.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CharacterScore {
+    /// Nombre d'envois du caractère.
+    pub sent: usize,
+    /// Nombre d'envois mal recopiés ou manqués.
+    pub errors: usize,
+}
+
+impl CharacterScore {
+    /// Retourne la part des envois mal recopiés, entre 0.0 et 1.0.
+    pub fn error_rate(&self) -> f32 {
+        if self.sent == 0 {
+            0.0
+        } else {
+            self.errors as f32 / self.sent as f32
+        }
+    }
+}
+
+/// Confusion entre un caractère envoyé et le caractère recopié à sa place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Confusion {
+    /// Caractère envoyé.
+    pub sent: char,
+    /// Caractère recopié.
+    pub copied: char,
+    /// Nombre de fois où la confusion a eu lieu.
+    pub count: usize,
+}
+
+/// Évaluation d'une copie, calculée par `score_copy`.
+///
+/// Les espaces servent à aligner les mots mais ne sont pas comptés comme des caractères.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyScore {
+    /// Alignement du texte envoyé et du texte recopié, dans l'ordre du texte.
+    pub edits: Vec<Edit>,
+    /// Nombre de caractères envoyés.
+    pub sent: usize,
+    /// Nombre de caractères correctement recopiés.
+    pub correct: usize,
+    /// Nombre de caractères recopiés comme un autre.
+    pub substitutions: usize,
+    /// Nombre de caractères envoyés qui n'ont pas été recopiés.
+    pub missed: usize,
+    /// Nombre de caractères recopiés en trop.
+    pub extra: usize,
+    /// Résultats de chaque caractère envoyé.
+    pub characters: BTreeMap<char, CharacterScore>,
+    /// Confusions, de la plus fréquente à la moins fréquente.
+    pub confusions: Vec<Confusion>,
+}
+
+impl CopyScore {
+    /// Retourne la précision de la copie, entre 0.0 et 1.0 : les caractères correctement
+    /// recopiés, rapportés aux caractères envoyés et aux caractères recopiés en trop (pour
+    /// qu'ajouter des caractères au hasard ne soit pas gratuit). Une copie vide d'un texte vide
+    /// est parfaite.
+    pub fn accuracy(&self) -> f32 {
+        let total: usize = self.sent + self.extra;
+        if total == 0 {
+            1.0
+        } else {
+            self.correct as f32 / total as f32
+        }
+    }
+
+    /// Retourne le score de la copie ajusté à la vitesse d'envoi : la précision multipliée par
+    /// la vitesse en mots par minute. Recopier 90 % d'un message à 20 mots par minute vaut
+    /// ainsi 18, autant que recopier parfaitement un message à 18 mots par minute.
+    pub fn wpm_score(&self, wpm: f32) -> f32 {
+        self.accuracy() * wpm
+    }
+
+    /// Retourne au plus `count` confusions parmi les plus fréquentes.
+    pub fn most_confused(&self, count: usize) -> &[Confusion] {
+        &self.confusions[..count.min(self.confusions.len())]
+    }
+}
+
+/// Compare la copie d'un élève au texte envoyé.
+///
+/// Les deux textes sont mis en majuscules et leurs espaces répétés réduits, puis alignés
+/// caractère par caractère (distance de Levenshtein, avec l'alignement de Needleman-Wunsch) :
+/// chaque caractère envoyé est recopié, remplacé par un autre ou manqué, et les caractères
+/// restants de la copie sont en trop. Un espace n'est jamais aligné avec un autre caractère,
+/// pour que les mots restent alignés entre eux.
+///
+/// # Arguments
+///
+/// * `sent` - Le texte envoyé.
+/// * `copied` - Le texte recopié par l'élève.
+///
+/// # Exemple
+///
+/// ```rust
+/// use crate::morseus::{score_copy, Confusion, CopyScore, Edit};
+///
+/// fn main() {
+///     let score: CopyScore = score_copy("HB6 SBH", "5B6 S6H5");
+///     assert_eq!(score.sent, 6);
+///     assert_eq!(score.correct, 4);
+///     assert_eq!(score.substitutions, 2);
+///     assert_eq!(score.extra, 1);
+///     assert_eq!(score.edits[0], Edit::Substitution { sent: 'H', copied: '5' });
+///     assert_eq!(score.characters[&'H'].errors, 1);
+///     assert_eq!(score.characters[&'B'].error_rate(), 0.5);
+///     assert_eq!(score.most_confused(1), &[Confusion { sent: 'B', copied: '6', count: 1 }]);
+///     assert!((score.accuracy() - 4.0 / 7.0).abs() < 1e-6);
+///     assert!((score.wpm_score(21.0) - 12.0).abs() < 1e-4);
+/// }
+/// ```
+pub fn score_copy(sent: &str, copied: &str) -> CopyScore {
+    let sent: Vec<char> = normalize(sent);
+    let copied: Vec<char> = normalize(copied);
+    let edits: Vec<Edit> = align(&sent, &copied);
+
+    let mut score = CopyScore {
+        edits: Vec::new(),
+        sent: 0,
+        correct: 0,
+        substitutions: 0,
+        missed: 0,
+        extra: 0,
+        characters: BTreeMap::new(),
+        confusions: Vec::new(),
+    };
+    let mut confusions: HashMap<(char, char), usize> = HashMap::new();
+    for edit in &edits {
+        let (sent, error) = match *edit {
+            Edit::Match(' ') | Edit::Missed(' ') | Edit::Extra(' ') => continue,
+            Edit::Match(character) => {
+                score.correct += 1;
+                (character, false)
+            }
+            Edit::Substitution { sent, copied } => {
+                score.substitutions += 1;
+                *confusions.entry((sent, copied)).or_insert(0) += 1;
+                (sent, true)
+            }
+            Edit::Missed(character) => {
+                score.missed += 1;
+                (character, true)
+            }
+            Edit::Extra(_) => {
+                score.extra += 1;
+                continue;
+            }
+        };
+        score.sent += 1;
+        let character: &mut CharacterScore = score.characters.entry(sent).or_default();
+        character.sent += 1;
+        character.errors += error as usize;
+    }
+    score.confusions = confusions
+        .into_iter()
+        .map(|((sent, copied), count)| Confusion {
+            sent,
+            copied,
+            count,
+        })
+        .collect();
+    score.confusions.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(a.sent.cmp(&b.sent))
+            .then(a.copied.cmp(&b.copied))
+    });
+    score.edits = edits;
+    score
+}
+
+/// Met un texte en majuscules et réduit ses espaces à un seul espace entre les mots.
+fn normalize(text: &str) -> Vec<char> {
+    text.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .chars()
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Le chemin de coût minimal arrive en diagonale : caractère reçu ou remplacé.
+const DIAGONAL: u8 = 0;
+/// Le chemin arrive d'en haut : caractère manqué.
+const UP: u8 = 1;
+/// Le chemin arrive de la gauche : caractère en trop.
+const LEFT: u8 = 2;
+
+/// Aligne deux suites de caractères avec un nombre minimal d'opérations.
+///
+/// Seules deux lignes de coûts sont gardées ; le chemin est retrouvé grâce à la direction
+/// retenue pour chaque case, sur un octet.
+fn align(sent: &[char], copied: &[char]) -> Vec<Edit> {
+    // Coût d'un remplacement ; un espace n'est jamais remplacé par un autre caractère
+    let substitution = |a: char, b: char| -> usize {
+        match (a == b, a == ' ' || b == ' ') {
+            (true, _) => 0,
+            (false, false) => 1,
+            (false, true) => 2,
+        }
+    };
+    let columns: usize = copied.len() + 1;
+    let mut previous: Vec<usize> = (0..columns).collect();
+    let mut current: Vec<usize> = vec![0; columns];
+    let mut directions: Vec<u8> = vec![LEFT; (sent.len() + 1) * columns];
+    for i in 1..=sent.len() {
+        current[0] = i;
+        directions[i * columns] = UP;
+        for j in 1..=copied.len() {
+            let diagonal: usize = substitution(sent[i - 1], copied[j - 1]);
+            let cost: usize = (previous[j - 1] + diagonal)
+                .min(previous[j] + 1)
+                .min(current[j - 1] + 1);
+            // En cas d'égalité, les remplacements sont préférés pour relever les confusions
+            directions[i * columns + j] = if diagonal < 2 && cost == previous[j - 1] + diagonal {
+                DIAGONAL
+            } else if cost == previous[j] + 1 {
+                UP
+            } else {
+                LEFT
+            };
+            current[j] = cost;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    // Remonte le chemin de coût minimal
+    let mut edits: Vec<Edit> = Vec::new();
+    let (mut i, mut j) = (sent.len(), copied.len());
+    while i > 0 || j > 0 {
+        let direction: u8 = directions[i * columns + j];
+        if direction == DIAGONAL {
+            edits.push(if sent[i - 1] == copied[j - 1] {
+                Edit::Match(sent[i - 1])
+            } else {
+                Edit::Substitution {
+                    sent: sent[i - 1],
+                    copied: copied[j - 1],
+                }
+            });
+            i -= 1;
+            j -= 1;
+        } else if direction == UP {
+            edits.push(Edit::Missed(sent[i - 1]));
+            i -= 1;
+        } else {
+            edits.push(Edit::Extra(copied[j - 1]));
+            j -= 1;
+        }
+    }
+    edits.reverse();
+    edits
+}
@@ -0,0 +1,111 @@
+use rodio::{OutputStream, Sink, Source};
+
+use std::{
+    f32::consts::PI,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::{player::PlayerError, timeline::Tone};
+
+/// Tonalité d'écoute d'un manipulateur : le son est émis tant que la clé est enfoncée.
+///
+/// Contrairement à un `Player`, la durée des signaux n'est pas connue à l'avance : la clé est
+/// enfoncée et relâchée au fil de l'eau avec `key_down` et `key_up`. Le son monte et descend
+/// en 5 ms pour éviter les claquements. Comme pour un `Player`, le flux audio doit rester sur
+/// le thread qui l'a ouvert.
+pub struct Sidetone {
+    _stream: OutputStream,
+    _sink: Sink,
+    key: Arc<AtomicBool>,
+}
+
+impl Sidetone {
+    /// Ouvre le dispositif audio par défaut et démarre une tonalité silencieuse.
+    pub(crate) fn new(tone: Tone, sampling_rate: u32) -> Result<Self, PlayerError> {
+        let (stream, stream_handle) = OutputStream::try_default()?;
+        let sink: Sink = Sink::try_new(&stream_handle)?;
+        let key: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+        sink.append(SidetoneSource {
+            key: Arc::clone(&key),
+            frequency: tone.frequency,
+            amplification: tone.amplification,
+            sampling_rate,
+            phase: 0.0,
+            level: 0.0,
+        });
+        Ok(Sidetone {
+            _stream: stream,
+            _sink: sink,
+            key,
+        })
+    }
+
+    /// Enfonce la clé : la tonalité commence.
+    pub fn key_down(&self) {
+        self.key.store(true, Ordering::SeqCst);
+    }
+
+    /// Relâche la clé : la tonalité s'arrête.
+    pub fn key_up(&self) {
+        self.key.store(false, Ordering::SeqCst);
+    }
+
+    /// Indique si la clé est enfoncée.
+    pub fn is_key_down(&self) -> bool {
+        self.key.load(Ordering::SeqCst)
+    }
+}
+
+/// Source audio sans fin qui suit l'état de la clé partagée avec un `Sidetone`.
+struct SidetoneSource {
+    key: Arc<AtomicBool>,
+    frequency: f32,
+    amplification: f32,
+    sampling_rate: u32,
+    phase: f32,
+    level: f32,
+}
+
+impl SidetoneSource {
+    /// Durée de la montée et de la descente du son.
+    const RAMP: f32 = 0.005;
+}
+
+impl Iterator for SidetoneSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let target: f32 = if self.key.load(Ordering::Relaxed) {
+            1.0
+        } else {
+            0.0
+        };
+        let step: f32 = 1.0 / (Self::RAMP * self.sampling_rate as f32);
+        self.level += (target - self.level).clamp(-step, step);
+        self.phase =
+            (self.phase + 2.0 * PI * self.frequency / self.sampling_rate as f32) % (2.0 * PI);
+        Some(self.phase.sin() * self.amplification * self.level)
+    }
+}
+
+impl Source for SidetoneSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sampling_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
@@ -0,0 +1,249 @@
+use hound::{Error as HoundError, SampleFormat, WavSpec, WavWriter};
+
+#[cfg(feature = "playback")]
+use rodio::{buffer::SamplesBuffer, OutputStream, Sink};
+
+use std::{
+    convert::Infallible,
+    fs::File,
+    io::{BufWriter, Seek, Write},
+    time::Duration,
+};
+
+#[cfg(feature = "playback")]
+use crate::player::PlayerError;
+use crate::{audio::quantize, timeline::Element};
+
+/// Destination des échantillons audio produits par `Morse`.
+///
+/// `Morse` écrit les éléments un par un, dans l'ordre, puis appelle `finish` une fois le
+/// message terminé.
+pub trait AudioSink {
+    /// Erreur retournée par la destination.
+    type Error;
+
+    /// Reçoit un élément et les échantillons qui le composent.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - L'élément joué.
+    /// * `samples` - Les échantillons de l'élément.
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons.
+    fn write(
+        &mut self,
+        element: &Element,
+        samples: &[f32],
+        sampling_rate: u32,
+    ) -> Result<(), Self::Error>;
+
+    /// Indique que le message est terminé.
+    fn finish(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Destination qui ignore tous les échantillons.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NullSink;
+
+impl AudioSink for NullSink {
+    type Error = Infallible;
+
+    fn write(&mut self, _: &Element, _: &[f32], _: u32) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// Destination qui conserve en mémoire les éléments et les échantillons reçus.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    elements: Vec<Element>,
+    samples: Vec<f32>,
+    sampling_rate: u32,
+    finished: bool,
+}
+
+impl MemorySink {
+    /// Crée une destination en mémoire vide.
+    pub fn new() -> Self {
+        MemorySink::default()
+    }
+
+    /// Retourne les éléments reçus, dans l'ordre.
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// Retourne tous les échantillons reçus.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Retourne le taux d'échantillonnage des derniers échantillons reçus.
+    pub fn sampling_rate(&self) -> u32 {
+        self.sampling_rate
+    }
+
+    /// Retourne la durée totale des éléments reçus.
+    pub fn duration(&self) -> Duration {
+        self.elements
+            .iter()
+            .map(|element| element.tone.duration)
+            .sum()
+    }
+
+    /// Indique si `finish` a été appelée.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+impl AudioSink for MemorySink {
+    type Error = Infallible;
+
+    fn write(
+        &mut self,
+        element: &Element,
+        samples: &[f32],
+        sampling_rate: u32,
+    ) -> Result<(), Infallible> {
+        self.elements.push(*element);
+        self.samples.extend_from_slice(samples);
+        self.sampling_rate = sampling_rate;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Infallible> {
+        self.finished = true;
+        Ok(())
+    }
+}
+
+/// Destination qui écrit les échantillons au fil de l'eau dans un fichier WAV 16 bits mono.
+///
+/// Contrairement à `Morse::write_wav`, aucune normalisation n'est possible : les échantillons
+/// hors de la plage [-1.0, 1.0] sont écrêtés.
+pub struct WavSink<W: Write + Seek> {
+    writer: Option<WavWriter<W>>,
+    sampling_rate: u32,
+}
+
+impl WavSink<BufWriter<File>> {
+    /// Crée le fichier WAV `filename`.
+    pub fn create(filename: &str, sampling_rate: u32) -> Result<Self, HoundError> {
+        WavSink::new(BufWriter::new(File::create(filename)?), sampling_rate)
+    }
+}
+
+impl<W: Write + Seek> WavSink<W> {
+    /// Crée une destination qui écrit un fichier WAV dans le flux `writer`.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Morse, WavSink};
+    /// use std::io::Cursor;
+    ///
+    /// fn main() {
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     let mut buffer: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+    ///     let mut sink = WavSink::new(&mut buffer, 8000).unwrap();
+    ///     morse.play_morse_code_on(".", &mut sink).unwrap();
+    ///     drop(sink);
+    ///     // en-tête + bip court (0.5 s) et pause (0.5 s) en 16 bits
+    ///     assert_eq!(buffer.get_ref().len(), 44 + 8000 * 2);
+    /// }
+    /// ```
+    pub fn new(writer: W, sampling_rate: u32) -> Result<Self, HoundError> {
+        let spec: WavSpec = WavSpec {
+            channels: 1,
+            sample_rate: sampling_rate,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        Ok(WavSink {
+            writer: Some(WavWriter::new(writer, spec)?),
+            sampling_rate,
+        })
+    }
+}
+
+impl<W: Write + Seek> AudioSink for WavSink<W> {
+    type Error = HoundError;
+
+    /// Retourne `HoundError::Unsupported` si le taux d'échantillonnage diffère de celui du fichier.
+    fn write(
+        &mut self,
+        _: &Element,
+        samples: &[f32],
+        sampling_rate: u32,
+    ) -> Result<(), HoundError> {
+        if sampling_rate != self.sampling_rate {
+            return Err(HoundError::Unsupported);
+        }
+        let writer: &mut WavWriter<W> = self.writer.as_mut().ok_or(HoundError::Unsupported)?;
+        for &sample in samples {
+            writer.write_sample(quantize(sample.clamp(-1.0, 1.0), 16, None) as i16)?;
+        }
+        Ok(())
+    }
+
+    /// Finalise le fichier WAV ; les écritures suivantes échouent.
+    fn finish(&mut self) -> Result<(), HoundError> {
+        match self.writer.take() {
+            Some(writer) => writer.finalize(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Destination qui joue les échantillons sur le dispositif audio par défaut.
+///
+/// Le flux de sortie est ouvert une seule fois ; `finish` attend la fin de la lecture.
+#[cfg(feature = "playback")]
+pub struct RodioSink {
+    _stream: OutputStream,
+    sink: Sink,
+}
+
+#[cfg(feature = "playback")]
+impl RodioSink {
+    /// Nombre maximal d'éléments en attente de lecture.
+    const QUEUE_LENGTH: usize = 16;
+
+    /// Ouvre le dispositif audio par défaut.
+    pub fn try_default() -> Result<Self, PlayerError> {
+        let (stream, stream_handle) = OutputStream::try_default()?;
+        let sink: Sink = Sink::try_new(&stream_handle)?;
+        Ok(RodioSink {
+            _stream: stream,
+            sink,
+        })
+    }
+}
+
+#[cfg(feature = "playback")]
+impl AudioSink for RodioSink {
+    type Error = Infallible;
+
+    fn write(
+        &mut self,
+        _: &Element,
+        samples: &[f32],
+        sampling_rate: u32,
+    ) -> Result<(), Infallible> {
+        // Limite la quantité d'audio en attente pour garder une mémoire constante
+        while self.sink.len() >= Self::QUEUE_LENGTH {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        self.sink
+            .append(SamplesBuffer::new(1, sampling_rate, samples.to_vec()));
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), Infallible> {
+        self.sink.sleep_until_end();
+        Ok(())
+    }
+}
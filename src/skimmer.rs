@@ -0,0 +1,348 @@
+use std::{collections::VecDeque, time::Duration};
+
+use crate::{
+    audio_decoder::{DetectedTone, KeyDetector, TONE_RANGE},
+    spectrum::power_spectrum,
+    timing::{DecodedCharacter, TimingTracker},
+};
+
+/// Caractère décodé par un `Skimmer`, avec la fréquence de la station qui l'a envoyé.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkimmedCharacter {
+    /// Fréquence estimée de la station, en Hz.
+    pub frequency: f32,
+    /// Le caractère décodé.
+    pub character: DecodedCharacter,
+}
+
+/// Canal du banc de filtres : une bande de la transformée de Fourier.
+#[derive(Debug, Clone)]
+struct Channel {
+    bin: usize,
+    detector: KeyDetector,
+    history: VecDeque<bool>,
+    offset_sum: f32,
+    offset_count: usize,
+}
+
+/// Banc de filtres qui découpe la bande passante (100 à 3000 Hz) en canaux à l'aide de
+/// transformées de Fourier successives, et détecte l'état de la clé dans chaque canal.
+///
+/// Une station est attribuée à un seul canal, celui du pic de son spectre ; les canaux voisins
+/// qui ne reçoivent que ses fuites spectrales restent silencieux. Un canal n'est actif que si
+/// son niveau dépasse nettement le niveau médian du spectre.
+///
+/// Les signaux et les silences plus courts qu'une fenêtre d'analyse, comme ceux que causent les
+/// claquements d'une autre station, sont ignorés : l'état de chaque canal est donc retardé
+/// d'environ deux fenêtres.
+#[derive(Debug, Clone)]
+pub(crate) struct FilterBank {
+    sampling_rate: u32,
+    frame: Vec<f32>,
+    hop_length: usize,
+    pending: usize,
+    channels: Vec<Channel>,
+}
+
+impl FilterBank {
+    /// Largeur visée d'un canal, en Hz.
+    const CHANNEL_WIDTH: f32 = 50.0;
+    /// Nombre d'analyses par longueur de fenêtre.
+    const OVERLAP: usize = 4;
+    /// Rapport minimal entre le niveau d'un canal actif et le niveau médian du spectre.
+    const ACTIVE_RATIO: f32 = 4.0;
+    /// Rapport de niveau au-delà duquel le canal supérieur l'emporte sur un canal.
+    const PEAK_RATIO: f32 = 1.25;
+    /// Durée minimale d'un signal ou d'un silence, en nombre d'analyses (un peu plus d'une
+    /// fenêtre).
+    const MIN_RUN: usize = Self::OVERLAP + 1;
+
+    /// Crée un banc de filtres pour des échantillons au taux `sampling_rate`.
+    pub(crate) fn new(sampling_rate: u32) -> Self {
+        let frame_length: usize = ((sampling_rate as f32 / Self::CHANNEL_WIDTH) as usize)
+            .max(Self::OVERLAP)
+            .next_power_of_two();
+        let hop_length: usize = frame_length / Self::OVERLAP;
+        let resolution: f32 = sampling_rate as f32 / frame_length as f32;
+        let lowest: usize = ((TONE_RANGE.0 / resolution).ceil() as usize).max(1);
+        let highest: usize = ((TONE_RANGE.1 / resolution) as usize).min(frame_length / 2 - 1);
+        let hop_duration: Duration =
+            Duration::from_secs_f64(hop_length as f64 / sampling_rate.max(1) as f64);
+        FilterBank {
+            sampling_rate,
+            frame: vec![0.0; frame_length],
+            hop_length,
+            pending: 0,
+            channels: (lowest..=highest)
+                .map(|bin| Channel {
+                    bin,
+                    detector: KeyDetector::new(hop_duration),
+                    history: VecDeque::new(),
+                    offset_sum: 0.0,
+                    offset_count: 0,
+                })
+                .collect(),
+        }
+    }
+
+    /// Retourne le nombre de canaux.
+    pub(crate) fn channel_count(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Retourne la durée entre deux analyses.
+    pub(crate) fn hop_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.hop_length as f64 / self.sampling_rate.max(1) as f64)
+    }
+
+    /// Retourne la fréquence estimée de la station reçue sur un canal : le centre du canal,
+    /// corrigé par la position moyenne du pic pendant les signaux.
+    pub(crate) fn frequency(&self, channel: usize) -> f32 {
+        let channel: &Channel = &self.channels[channel];
+        let offset: f32 = if channel.offset_count > 0 {
+            channel.offset_sum / channel.offset_count as f32
+        } else {
+            0.0
+        };
+        (channel.bin as f32 + offset) * self.sampling_rate as f32 / self.frame.len() as f32
+    }
+
+    /// Retourne le rapport signal sur bruit d'un canal, en dB.
+    pub(crate) fn snr(&self, channel: usize) -> f32 {
+        self.channels[channel].detector.snr()
+    }
+
+    /// Ajoute des échantillons et appelle `on_hop` avec l'état de la clé de chaque canal après
+    /// chaque analyse.
+    pub(crate) fn push(&mut self, samples: &[f32], mut on_hop: impl FnMut(&Self, &[bool])) {
+        // Les nouveaux échantillons remplissent la fin de la fenêtre, qui n'est décalée qu'une
+        // fois par analyse
+        let start: usize = self.frame.len() - self.hop_length;
+        for &sample in samples {
+            self.frame[start + self.pending] = sample;
+            self.pending += 1;
+            if self.pending == self.hop_length {
+                self.pending = 0;
+                let keys: Vec<bool> = self.analyse();
+                on_hop(self, &keys);
+                self.frame.copy_within(self.hop_length.., 0);
+            }
+        }
+    }
+
+    /// Analyse la fenêtre courante et met à jour l'état de la clé de chaque canal.
+    fn analyse(&mut self) -> Vec<bool> {
+        // Amplitude d'une sinusoïde après une fenêtre de Hann de N échantillons : 4 * |X| / N
+        let scale: f32 = 4.0 / self.frame.len() as f32;
+        let levels: Vec<f32> = power_spectrum(&self.frame)
+            .into_iter()
+            .map(|power| scale * power.sqrt())
+            .collect();
+        let mut sorted: Vec<f32> = self
+            .channels
+            .iter()
+            .map(|channel| levels[channel.bin])
+            .collect();
+        sorted.sort_by(f32::total_cmp);
+        let floor: f32 = sorted.get(sorted.len() / 2).copied().unwrap_or(0.0);
+
+        self.channels
+            .iter_mut()
+            .map(|channel| {
+                let (below, level, above) = (
+                    levels[channel.bin - 1],
+                    levels[channel.bin],
+                    levels[channel.bin + 1],
+                );
+                // Un seul canal possède chaque pic, même lorsque la tonalité tombe entre deux
+                // bandes
+                let peak: bool = level >= above / Self::PEAK_RATIO
+                    && level > below * Self::PEAK_RATIO
+                    && level > Self::ACTIVE_RATIO * floor;
+                let key_down: bool = channel.detector.update(level, peak);
+                if key_down && peak {
+                    let (below, at, above) = (
+                        below.max(f32::MIN_POSITIVE).ln(),
+                        level.max(f32::MIN_POSITIVE).ln(),
+                        above.max(f32::MIN_POSITIVE).ln(),
+                    );
+                    let curvature: f32 = below - 2.0 * at + above;
+                    if curvature < 0.0 {
+                        channel.offset_sum += (0.5 * (below - above) / curvature).clamp(-1.0, 1.0);
+                        channel.offset_count += 1;
+                    }
+                }
+                Self::filter_runs(&mut channel.history, key_down)
+            })
+            .collect()
+    }
+
+    /// Ajoute l'état de la clé à l'historique d'un canal et retourne l'état retardé, après
+    /// avoir comblé les silences trop courts entre deux signaux, puis effacé les signaux trop
+    /// courts entre deux silences.
+    fn filter_runs(history: &mut VecDeque<bool>, key_down: bool) -> bool {
+        let run: usize = history
+            .iter()
+            .rev()
+            .take_while(|key| **key != key_down)
+            .count();
+        if key_down {
+            // Un silence court entre deux signaux est comblé ; le silence initial est conservé
+            if run > 0 && run < Self::MIN_RUN && run < history.len() {
+                history
+                    .iter_mut()
+                    .rev()
+                    .take(run)
+                    .for_each(|key| *key = true);
+            }
+        } else {
+            // Un signal court n'est effacé qu'une fois le silence qui le suit assez long pour
+            // ne plus être comblé
+            let gap: usize = history.iter().rev().take_while(|key| !**key).count() + 1;
+            if gap == Self::MIN_RUN {
+                let mark: usize = history
+                    .iter()
+                    .rev()
+                    .skip(gap - 1)
+                    .take_while(|key| **key)
+                    .count();
+                if mark > 0 && mark < Self::MIN_RUN {
+                    history
+                        .iter_mut()
+                        .rev()
+                        .skip(gap - 1)
+                        .take(mark)
+                        .for_each(|key| *key = false);
+                }
+            }
+        }
+        history.push_back(key_down);
+        if history.len() > 2 * Self::MIN_RUN {
+            history.pop_front().unwrap_or(false)
+        } else {
+            false
+        }
+    }
+}
+
+/// Décodeur de plusieurs stations simultanées sur une large bande audio.
+///
+/// La bande de 100 à 3000 Hz est découpée en canaux d'environ 30 à 45 Hz par un banc de
+/// filtres (transformées de Fourier successives, fenêtre d'environ 20 à 30 ms). Chaque canal
+/// où une station apparaît reçoit son propre décodeur de durées, qui suit la vitesse de cette
+/// station. Les stations doivent être séparées d'au moins trois canaux (environ 100 Hz).
+#[derive(Debug, Clone)]
+pub struct Skimmer {
+    bank: FilterBank,
+    template: TimingTracker,
+    decoders: Vec<Option<TimingTracker>>,
+    time: Duration,
+}
+
+impl Skimmer {
+    pub(crate) fn new(sampling_rate: u32, template: TimingTracker) -> Self {
+        let bank: FilterBank = FilterBank::new(sampling_rate);
+        Skimmer {
+            decoders: vec![None; bank.channel_count()],
+            bank,
+            template,
+            time: Duration::ZERO,
+        }
+    }
+
+    /// Ajoute des échantillons et retourne les caractères décodés depuis le dernier appel, sur
+    /// toutes les stations.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Les nouveaux échantillons, au taux d'échantillonnage du décodeur.
+    pub fn push(&mut self, samples: &[f32]) -> Vec<SkimmedCharacter> {
+        let mut skimmed: Vec<SkimmedCharacter> = Vec::new();
+        let (decoders, template, time) = (&mut self.decoders, &self.template, &mut self.time);
+        self.bank.push(samples, |bank, keys| {
+            let hop: Duration = bank.hop_duration();
+            for (channel, (decoder, &key_down)) in decoders.iter_mut().zip(keys).enumerate() {
+                // Le décodeur d'un canal est créé au premier signal reçu sur ce canal
+                if decoder.is_none() && key_down {
+                    let mut tracker: TimingTracker = template.clone();
+                    tracker.update(false, *time);
+                    *decoder = Some(tracker);
+                }
+                if let Some(tracker) = decoder {
+                    skimmed.extend(tracker.update(key_down, hop).into_iter().map(|character| {
+                        SkimmedCharacter {
+                            frequency: bank.frequency(channel),
+                            character,
+                        }
+                    }));
+                }
+            }
+            *time += hop;
+        });
+        skimmed
+    }
+
+    /// Termine le flux et retourne les derniers caractères décodés.
+    pub fn finish(&mut self) -> Vec<SkimmedCharacter> {
+        let mut skimmed: Vec<SkimmedCharacter> = Vec::new();
+        for (channel, decoder) in self.decoders.iter_mut().enumerate() {
+            if let Some(tracker) = decoder {
+                skimmed.extend(
+                    tracker
+                        .finish()
+                        .into_iter()
+                        .map(|character| SkimmedCharacter {
+                            frequency: self.bank.frequency(channel),
+                            character,
+                        }),
+                );
+            }
+        }
+        skimmed
+    }
+
+    /// Retourne les fréquences des stations reçues jusqu'ici, dans l'ordre croissant.
+    pub fn stations(&self) -> Vec<f32> {
+        self.decoders
+            .iter()
+            .enumerate()
+            .filter(|(_, decoder)| decoder.is_some())
+            .map(|(channel, _)| self.bank.frequency(channel))
+            .collect()
+    }
+
+    /// Retourne la durée totale des échantillons analysés.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+/// Découpe des échantillons en canaux et retourne, pour chaque canal où un signal a été reçu,
+/// la tonalité de la station et ses durées de manipulation.
+pub(crate) fn channel_runs(
+    samples: &[f32],
+    sampling_rate: u32,
+) -> Vec<(DetectedTone, Vec<(bool, Duration)>)> {
+    let mut bank: FilterBank = FilterBank::new(sampling_rate);
+    let mut runs: Vec<Vec<(bool, Duration)>> = vec![Vec::new(); bank.channel_count()];
+    bank.push(samples, |bank, keys| {
+        let hop: Duration = bank.hop_duration();
+        for (runs, &key_down) in runs.iter_mut().zip(keys) {
+            match runs.last_mut() {
+                Some((last, duration)) if *last == key_down => *duration += hop,
+                _ => runs.push((key_down, hop)),
+            }
+        }
+    });
+    runs.into_iter()
+        .enumerate()
+        .filter(|(_, runs)| runs.iter().any(|(key_down, _)| *key_down))
+        .map(|(channel, runs)| {
+            let tone: DetectedTone = DetectedTone {
+                frequency: bank.frequency(channel),
+                snr: bank.snr(channel),
+            };
+            (tone, runs)
+        })
+        .collect()
+}
@@ -0,0 +1,97 @@
+#[cfg(feature = "playback")]
+use std::time::Duration;
+
+use crate::timeline::Element;
+
+/// Source d'échantillons audio générés à la demande à partir d'une séquence d'éléments Morse.
+///
+/// Les éléments ne sont lus qu'au moment où leurs échantillons sont nécessaires, ce qui permet
+/// de produire l'audio de messages arbitrairement longs avec une mémoire constante. Lorsque la
+/// fonctionnalité `playback` est activée, `MorseSource` implémente aussi `rodio::Source` et peut
+/// être jouée directement sur un `rodio::Sink`.
+pub struct MorseSource {
+    elements: Box<dyn Iterator<Item = Element> + Send>,
+    current: Option<Element>,
+    index: u64,
+    length: u64,
+    sampling_rate: u32,
+}
+
+impl MorseSource {
+    /// Crée une source à partir d'une séquence d'éléments.
+    ///
+    /// # Arguments
+    ///
+    /// * `elements` - Les éléments à jouer, consommés au fur et à mesure.
+    /// * `sampling_rate` - Le taux d'échantillonnage des échantillons produits.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Element, ElementKind, MorseSource, Tone};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let dot = Element::new(ElementKind::Dot, Tone::new(600.0, Duration::from_millis(10), 0.5));
+    ///     let source = MorseSource::new(vec![dot, dot], 8000);
+    ///     assert_eq!(source.count(), 160);
+    /// }
+    /// ```
+    pub fn new<I>(elements: I, sampling_rate: u32) -> Self
+    where
+        I: IntoIterator<Item = Element>,
+        I::IntoIter: Send + 'static,
+    {
+        MorseSource {
+            elements: Box::new(elements.into_iter()),
+            current: None,
+            index: 0,
+            length: 0,
+            sampling_rate,
+        }
+    }
+
+    /// Retourne le taux d'échantillonnage des échantillons produits.
+    pub fn sampling_rate(&self) -> u32 {
+        self.sampling_rate
+    }
+}
+
+impl Iterator for MorseSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        // Passe à l'élément suivant lorsque le courant est terminé (en sautant les éléments vides)
+        while self.index >= self.length {
+            let element: Element = self.elements.next()?;
+            self.current = Some(element);
+            self.index = 0;
+            self.length = element.tone.sample_count(self.sampling_rate);
+        }
+        let sample: f32 = self
+            .current
+            .map(|element| element.tone.sample(self.index, self.sampling_rate))
+            .unwrap_or(0.0);
+        self.index += 1;
+        Some(sample)
+    }
+}
+
+#[cfg(feature = "playback")]
+impl rodio::Source for MorseSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sampling_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
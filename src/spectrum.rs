@@ -0,0 +1,62 @@
+use std::f32::consts::PI;
+
+/// Transformée de Fourier rapide (radix 2, en place) d'un signal complexe.
+///
+/// `real` et `imaginary` doivent avoir la même longueur, qui doit être une puissance de deux.
+pub(crate) fn fft(real: &mut [f32], imaginary: &mut [f32]) {
+    let length: usize = real.len();
+    debug_assert!(length.is_power_of_two() && imaginary.len() == length);
+    // Permutation par inversion des bits
+    let mut j: usize = 0;
+    for i in 1..length {
+        let mut bit: usize = length >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            real.swap(i, j);
+            imaginary.swap(i, j);
+        }
+    }
+    // Papillons
+    let mut size: usize = 2;
+    while size <= length {
+        let angle: f32 = -2.0 * PI / size as f32;
+        for start in (0..length).step_by(size) {
+            for k in 0..size / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (even, odd) = (start + k, start + k + size / 2);
+                let odd_real: f32 = real[odd] * cos - imaginary[odd] * sin;
+                let odd_imaginary: f32 = real[odd] * sin + imaginary[odd] * cos;
+                real[odd] = real[even] - odd_real;
+                imaginary[odd] = imaginary[even] - odd_imaginary;
+                real[even] += odd_real;
+                imaginary[even] += odd_imaginary;
+            }
+        }
+        size <<= 1;
+    }
+}
+
+/// Calcule le spectre de puissance d'un bloc d'échantillons réels après une fenêtre de Hann.
+///
+/// Le bloc est complété par des zéros jusqu'à la puissance de deux suivante ; seule la moitié
+/// utile du spectre (de 0 à la fréquence de Nyquist) est retournée.
+pub(crate) fn power_spectrum(block: &[f32]) -> Vec<f32> {
+    let length: usize = block.len().max(2).next_power_of_two();
+    let mut real: Vec<f32> = vec![0.0; length];
+    let mut imaginary: Vec<f32> = vec![0.0; length];
+    let denominator: f32 = (block.len().max(2) - 1) as f32;
+    for (index, sample) in block.iter().enumerate() {
+        let window: f32 = 0.5 - 0.5 * (2.0 * PI * index as f32 / denominator).cos();
+        real[index] = sample * window;
+    }
+    fft(&mut real, &mut imaginary);
+    real.iter()
+        .zip(&imaginary)
+        .take(length / 2 + 1)
+        .map(|(re, im)| re * re + im * im)
+        .collect()
+}
@@ -0,0 +1,607 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+/// Un son sinusoïdal défini par sa fréquence, sa durée et son amplification.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tone {
+    /// Fréquence du son en Hz (0.0 pour un silence).
+    pub frequency: f32,
+    /// Durée du son.
+    pub duration: Duration,
+    /// Amplification appliquée au son (entre 0.0 et 1.0).
+    pub amplification: f32,
+}
+
+impl Tone {
+    /// Crée un nouveau son.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Tone;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let tone = Tone::new(440.0, Duration::from_millis(100), 0.5);
+    ///     assert_eq!(tone.frequency, 440.0);
+    /// }
+    /// ```
+    pub fn new(frequency: f32, duration: Duration, amplification: f32) -> Self {
+        Tone {
+            frequency,
+            duration,
+            amplification,
+        }
+    }
+
+    /// Crée un silence de la durée donnée.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Tone;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let silence = Tone::silence(Duration::from_millis(100));
+    ///     assert_eq!(silence.amplification, 0.0);
+    /// }
+    /// ```
+    pub fn silence(duration: Duration) -> Self {
+        Tone::new(0.0, duration, 0.0)
+    }
+
+    /// Retourne le nombre d'échantillons du son au taux d'échantillonnage donné.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Tone;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let tone = Tone::new(440.0, Duration::from_millis(100), 0.5);
+    ///     assert_eq!(tone.sample_count(44100), 4410);
+    /// }
+    /// ```
+    pub fn sample_count(&self, sampling_rate: u32) -> u64 {
+        (self.duration.as_secs_f64() * sampling_rate as f64).round() as u64
+    }
+
+    /// Retourne la valeur du n-ième échantillon du son au taux d'échantillonnage donné.
+    pub(crate) fn sample(&self, index: u64, sampling_rate: u32) -> f32 {
+        let time: f64 = index as f64 / sampling_rate as f64;
+        let phase: f64 = 2.0 * std::f64::consts::PI * self.frequency as f64 * time;
+        phase.sin() as f32 * self.amplification
+    }
+}
+
+/// Type d'un élément d'une séquence Morse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    /// Un bip court, joué pour un point ('.').
+    Dot,
+    /// Un bip long, joué pour un tiret ('-').
+    Dash,
+    /// Un bip silencieux, joué pour tout autre caractère (' ', '/').
+    Silence,
+    /// La pause qui suit chaque signal.
+    Pause,
+}
+
+/// Un élément d'une séquence Morse : le type de signal et le son à produire.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Element {
+    /// Type de l'élément.
+    pub kind: ElementKind,
+    /// Son produit par l'élément.
+    pub tone: Tone,
+}
+
+impl Element {
+    /// Crée un nouvel élément.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Element, ElementKind, Tone};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let element = Element::new(ElementKind::Dot, Tone::new(600.0, Duration::from_millis(60), 0.5));
+    ///     assert_eq!(element.kind, ElementKind::Dot);
+    /// }
+    /// ```
+    pub fn new(kind: ElementKind, tone: Tone) -> Self {
+        Element { kind, tone }
+    }
+
+    /// Indique si l'élément produit un son (point ou tiret).
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Element, ElementKind, Tone};
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let pause = Element::new(ElementKind::Pause, Tone::silence(Duration::from_millis(60)));
+    ///     assert!(!pause.is_key_down());
+    /// }
+    /// ```
+    pub fn is_key_down(&self) -> bool {
+        matches!(self.kind, ElementKind::Dot | ElementKind::Dash)
+    }
+}
+
+/// Sons utilisés pour convertir un code Morse en éléments.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Tones {
+    pub(crate) short_beep: Tone,
+    pub(crate) long_beep: Tone,
+    pub(crate) silence: Tone,
+    pub(crate) word_silence: Tone,
+    pub(crate) pause: Duration,
+}
+
+impl Tones {
+    /// Retourne l'élément associé à un symbole du code Morse.
+    pub(crate) fn element(&self, symbol: char) -> Element {
+        match symbol {
+            '.' => Element::new(ElementKind::Dot, self.short_beep),
+            '-' => Element::new(ElementKind::Dash, self.long_beep),
+            '/' => Element::new(ElementKind::Silence, self.word_silence),
+            _ => Element::new(ElementKind::Silence, self.silence),
+        }
+    }
+
+    /// Retourne la pause qui suit chaque signal.
+    pub(crate) fn pause(&self) -> Element {
+        Element::new(ElementKind::Pause, Tone::silence(self.pause))
+    }
+}
+
+/// Repère placé dans une séquence Morse pour relier l'audio au message d'origine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Marker {
+    /// Un caractère commence.
+    CharacterStarted {
+        /// Position du caractère dans le message : dans le texte d'origine pour un texte,
+        /// parmi les caractères Morse pour un code Morse.
+        index: usize,
+        /// Le caractère, s'il est connu de l'alphabet.
+        character: Option<char>,
+        /// Le code Morse du caractère.
+        code: String,
+    },
+    /// Un mot vient de se terminer.
+    WordFinished {
+        /// Position du mot dans le message.
+        index: usize,
+    },
+}
+
+/// Étape d'une séquence Morse : un élément à jouer ou un repère.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Step {
+    Element(Element),
+    Marker(Marker),
+}
+
+impl Step {
+    /// Retourne l'élément de l'étape, s'il y en a un.
+    pub(crate) fn element(self) -> Option<Element> {
+        match self {
+            Step::Element(element) => Some(element),
+            Step::Marker(_) => None,
+        }
+    }
+}
+
+/// Itérateur paresseux sur les étapes d'un code Morse : chaque symbole est suivi d'une pause,
+/// et des repères signalent le début des caractères et la fin des mots.
+#[derive(Debug, Clone)]
+pub(crate) struct MorseCodeSteps {
+    morse_code: String,
+    position: usize,
+    pending: VecDeque<Step>,
+    characters: HashMap<String, char>,
+    tones: Tones,
+    character_index: usize,
+    word_index: usize,
+    in_character: bool,
+    word_has_character: bool,
+}
+
+impl MorseCodeSteps {
+    pub(crate) fn new(morse_code: String, characters: HashMap<String, char>, tones: Tones) -> Self {
+        MorseCodeSteps {
+            morse_code,
+            position: 0,
+            pending: VecDeque::new(),
+            characters,
+            tones,
+            character_index: 0,
+            word_index: 0,
+            in_character: false,
+            word_has_character: false,
+        }
+    }
+
+    /// Termine le mot en cours en ajoutant un repère `WordFinished`.
+    fn finish_word(&mut self) {
+        if self.word_has_character {
+            self.pending.push_back(Step::Marker(Marker::WordFinished {
+                index: self.word_index,
+            }));
+            self.word_index += 1;
+            self.word_has_character = false;
+        }
+    }
+}
+
+impl Iterator for MorseCodeSteps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        if let Some(step) = self.pending.pop_front() {
+            return Some(step);
+        }
+        let rest: &str = &self.morse_code[self.position..];
+        let Some(symbol) = rest.chars().next() else {
+            // Fin du code : le dernier mot est terminé
+            self.finish_word();
+            return self.pending.pop_front();
+        };
+        let is_signal: bool = symbol == '.' || symbol == '-';
+        if is_signal && !self.in_character {
+            let code: String = rest
+                .chars()
+                .take_while(|c| *c == '.' || *c == '-')
+                .collect();
+            self.pending
+                .push_back(Step::Marker(Marker::CharacterStarted {
+                    index: self.character_index,
+                    character: self.characters.get(&code).copied(),
+                    code,
+                }));
+            self.character_index += 1;
+            self.word_has_character = true;
+        }
+        self.in_character = is_signal;
+        if symbol == '/' {
+            self.finish_word();
+        }
+        self.position += symbol.len_utf8();
+        self.pending
+            .push_back(Step::Element(self.tones.element(symbol)));
+        self.pending.push_back(Step::Element(self.tones.pause()));
+        self.pending.pop_front()
+    }
+}
+
+/// Lit un signe de procédure écrit entre chevrons au début d'un texte, comme `<SK>` : les
+/// codes de ses lettres sont enchaînés sans silence entre eux.
+///
+/// Retourne le code du signe et la longueur (en octets) du texte lu, chevrons compris, ou
+/// `None` si le texte ne commence pas par un signe dont toutes les lettres sont connues.
+pub(crate) fn prosign<F>(text: &str, code_of: F) -> Option<(String, usize)>
+where
+    F: Fn(char) -> Option<String>,
+{
+    let inner: &str = text.strip_prefix('<')?;
+    let end: usize = inner.find('>')?;
+    let letters: &str = &inner[..end];
+    if letters.chars().count() < 2 || letters.contains(char::is_whitespace) {
+        return None;
+    }
+    let code: String = letters
+        .chars()
+        .map(|letter| code_of(letter).filter(|code| code != "/"))
+        .collect::<Option<String>>()?;
+    Some((code, end + 2))
+}
+
+/// Itérateur paresseux sur les étapes d'un texte : chaque caractère est encodé avec l'alphabet
+/// au moment où il est atteint, et les repères utilisent les positions dans le texte d'origine.
+///
+/// Les éléments produits sont les mêmes que pour le code Morse retourné par `Morse::encode`.
+#[derive(Debug, Clone)]
+pub(crate) struct TextSteps {
+    text: String,
+    position: usize,
+    character_index: usize,
+    pending: VecDeque<Step>,
+    codes: HashMap<char, String>,
+    tones: Tones,
+    word_index: usize,
+    started: bool,
+    word_has_character: bool,
+}
+
+impl TextSteps {
+    pub(crate) fn new(text: String, codes: HashMap<char, String>, tones: Tones) -> Self {
+        TextSteps {
+            text,
+            position: 0,
+            character_index: 0,
+            pending: VecDeque::new(),
+            codes,
+            tones,
+            word_index: 0,
+            started: false,
+            word_has_character: false,
+        }
+    }
+
+    /// Termine le mot en cours en ajoutant un repère `WordFinished`.
+    fn finish_word(&mut self) {
+        if self.word_has_character {
+            self.pending.push_back(Step::Marker(Marker::WordFinished {
+                index: self.word_index,
+            }));
+            self.word_index += 1;
+            self.word_has_character = false;
+        }
+    }
+}
+
+impl Iterator for TextSteps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        while self.pending.is_empty() {
+            let Some(character) = self.text[self.position..].chars().next() else {
+                // Fin du texte : le dernier mot est terminé
+                self.finish_word();
+                return self.pending.pop_front();
+            };
+            let index: usize = self.character_index;
+            let (code, character, length): (String, Option<char>, usize) =
+                match prosign(&self.text[self.position..], |c| self.codes.get(&c).cloned()) {
+                    Some((code, length)) => (code, None, length),
+                    None => {
+                        // Les caractères absents de l'alphabet sont ignorés, comme dans `encode`
+                        let length: usize = character.len_utf8();
+                        let Some(code) = self.codes.get(&character).cloned() else {
+                            self.position += length;
+                            self.character_index += 1;
+                            continue;
+                        };
+                        (code, Some(character), length)
+                    }
+                };
+            self.character_index += self.text[self.position..self.position + length]
+                .chars()
+                .count();
+            self.position += length;
+            if self.started {
+                // Espace entre les caractères encodés
+                self.pending
+                    .push_back(Step::Element(self.tones.element(' ')));
+                self.pending.push_back(Step::Element(self.tones.pause()));
+            }
+            self.started = true;
+            if code == "/" {
+                self.finish_word();
+            } else {
+                self.pending
+                    .push_back(Step::Marker(Marker::CharacterStarted {
+                        index,
+                        character,
+                        code: code.clone(),
+                    }));
+                self.word_has_character = true;
+            }
+            for symbol in code.chars() {
+                self.pending
+                    .push_back(Step::Element(self.tones.element(symbol)));
+                self.pending.push_back(Step::Element(self.tones.pause()));
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// Étapes d'un message, donné sous forme de code Morse ou de texte.
+#[cfg(feature = "playback")]
+#[derive(Debug, Clone)]
+pub(crate) enum Steps {
+    MorseCode(MorseCodeSteps),
+    Text(TextSteps),
+}
+
+#[cfg(feature = "playback")]
+impl Iterator for Steps {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        match self {
+            Steps::MorseCode(steps) => steps.next(),
+            Steps::Text(steps) => steps.next(),
+        }
+    }
+}
+
+/// Un caractère du message d'origine et l'intervalle de temps pendant lequel il est joué.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subtitle {
+    /// Position du caractère dans le message d'origine.
+    pub index: usize,
+    /// Le caractère, s'il est connu de l'alphabet.
+    pub character: Option<char>,
+    /// Le code Morse du caractère.
+    pub code: String,
+    /// Instant où le premier signal du caractère commence.
+    pub start: Duration,
+    /// Instant où la pause qui suit le dernier signal du caractère se termine.
+    pub end: Duration,
+}
+
+/// Calcule les sous-titres (caractère et intervalle de temps) d'une séquence d'étapes.
+pub(crate) fn subtitles<I: Iterator<Item = Step>>(steps: I) -> Vec<Subtitle> {
+    let mut subtitles: Vec<Subtitle> = Vec::new();
+    let mut current: Option<Subtitle> = None;
+    let mut time: Duration = Duration::ZERO;
+    for step in steps {
+        match step {
+            Step::Marker(Marker::CharacterStarted {
+                index,
+                character,
+                code,
+            }) => {
+                subtitles.extend(current.take());
+                current = Some(Subtitle {
+                    index,
+                    character,
+                    code,
+                    start: time,
+                    end: time,
+                });
+            }
+            Step::Marker(Marker::WordFinished { .. }) => {}
+            Step::Element(element) => {
+                if element.kind == ElementKind::Silence {
+                    subtitles.extend(current.take());
+                }
+                time += element.tone.duration;
+                if let Some(subtitle) = current.as_mut() {
+                    subtitle.end = time;
+                }
+            }
+        }
+    }
+    subtitles.extend(current);
+    subtitles
+}
+
+/// Durée d'un caractère du message, calculée par `Morse::duration_of`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CharacterDuration {
+    /// Position du caractère dans le message d'origine.
+    pub index: usize,
+    /// Le caractère, s'il est connu de l'alphabet.
+    pub character: Option<char>,
+    /// Le code Morse du caractère.
+    pub code: String,
+    /// Durée du premier signal du caractère à la fin du dernier.
+    pub duration: Duration,
+    /// Durée du caractère, en points.
+    pub units: f64,
+}
+
+/// Durée d'un message, calculée par `Morse::duration_of` sans produire d'audio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageDuration {
+    /// Durée du message, jusqu'à la fin du dernier signal.
+    pub duration: Duration,
+    /// Durée totale des signaux (clé enfoncée).
+    pub key_down: Duration,
+    /// Durée du message, en points.
+    pub units: f64,
+    /// Nombre de mots du message.
+    pub words: usize,
+    /// Durée de chaque caractère joué, dans l'ordre du message.
+    pub characters: Vec<CharacterDuration>,
+}
+
+impl MessageDuration {
+    /// Retourne le nombre de caractères joués.
+    pub fn character_count(&self) -> usize {
+        self.characters.len()
+    }
+
+    /// Retourne la part du message pendant laquelle la clé est enfoncée, entre 0.0 et 1.0.
+    pub fn key_down_ratio(&self) -> f32 {
+        if self.duration.is_zero() {
+            0.0
+        } else {
+            (self.key_down.as_secs_f64() / self.duration.as_secs_f64()) as f32
+        }
+    }
+}
+
+/// Calcule la durée d'une séquence d'étapes, en comptant les points avec l'unité `unit`.
+pub(crate) fn message_duration<I: Iterator<Item = Step>>(
+    steps: I,
+    unit: Duration,
+) -> MessageDuration {
+    let in_units = |duration: Duration| -> f64 {
+        if unit.is_zero() {
+            0.0
+        } else {
+            duration.as_secs_f64() / unit.as_secs_f64()
+        }
+    };
+    let mut message = MessageDuration {
+        duration: Duration::ZERO,
+        key_down: Duration::ZERO,
+        units: 0.0,
+        words: 0,
+        characters: Vec::new(),
+    };
+    let mut time: Duration = Duration::ZERO;
+    // Instant où le caractère en cours commence, avec sa durée
+    let mut current: Option<(Duration, CharacterDuration)> = None;
+    for step in steps {
+        match step {
+            Step::Marker(Marker::CharacterStarted {
+                index,
+                character,
+                code,
+            }) => {
+                message
+                    .characters
+                    .extend(current.take().map(|(_, character)| character));
+                current = Some((
+                    time,
+                    CharacterDuration {
+                        index,
+                        character,
+                        code,
+                        duration: Duration::ZERO,
+                        units: 0.0,
+                    },
+                ));
+            }
+            Step::Marker(Marker::WordFinished { .. }) => message.words += 1,
+            Step::Element(element) => {
+                time += element.tone.duration;
+                if element.is_key_down() {
+                    message.key_down += element.tone.duration;
+                    message.duration = time;
+                    if let Some((start, character)) = current.as_mut() {
+                        character.duration = time - *start;
+                        character.units = in_units(character.duration);
+                    }
+                }
+            }
+        }
+    }
+    message
+        .characters
+        .extend(current.map(|(_, character)| character));
+    message.units = in_units(message.duration);
+    message
+}
+
+/// Multiplie une durée, arrondie à la nanoseconde.
+pub(crate) fn scaled(duration: Duration, factor: f32) -> Duration {
+    Duration::from_nanos((duration.as_nanos() as f64 * factor as f64).round() as u64)
+}
+
+/// Avance dans les étapes jusqu'au début du caractère à la position `index` (ou au-delà).
+///
+/// Les éléments et repères qui précèdent ce caractère sont ignorés.
+#[cfg(feature = "playback")]
+pub(crate) fn skip_to_character<I>(mut steps: I, index: usize) -> impl Iterator<Item = Step>
+where
+    I: Iterator<Item = Step>,
+{
+    let first: Option<Step> = steps.by_ref().find(|step| {
+        matches!(step, Step::Marker(Marker::CharacterStarted { index: i, .. }) if *i >= index)
+    });
+    first.into_iter().chain(steps)
+}
@@ -0,0 +1,752 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::audio_decoder::DetectedTone;
+
+/// Message décodé à partir d'un signal (audio, durées de manipulation, etc.).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedMessage {
+    /// Le texte décodé.
+    pub text: String,
+    /// Le code Morse reconnu, avec un espace entre les caractères et '/' entre les mots.
+    pub morse_code: String,
+    /// Durée estimée d'un point à la fin du message.
+    pub dit: Duration,
+    /// Vitesse estimée en mots par minute à la fin du message (norme PARIS : un point dure
+    /// 1.2 / WPM secondes).
+    pub wpm: f32,
+    /// Les caractères décodés, avec leur position dans le temps et la vitesse estimée au
+    /// moment de leur décodage.
+    pub characters: Vec<DecodedCharacter>,
+    /// Tonalité détectée, lorsque la détection automatique est activée (voir
+    /// `Morse::set_tone_detection`).
+    pub tone: Option<DetectedTone>,
+}
+
+impl DecodedMessage {
+    /// Retourne les caractères décodés dont la confiance est inférieure à `threshold`, pour les
+    /// signaler à l'utilisateur.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - La confiance minimale, entre 0.0 et 1.0.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let morse = Morse::new();
+    ///     let ms = Duration::from_millis;
+    ///     let timings = [
+    ///         (true, ms(100)), (false, ms(100)), (true, ms(300)), // A (.-)
+    ///         (false, ms(300)),
+    ///         (true, ms(300)), (false, ms(100)), (true, ms(100)), // N (-.)
+    ///         (false, ms(300)),
+    ///         (true, ms(100)), (false, ms(100)), (true, ms(190)), // A ou I ? (.- ou ..)
+    ///     ];
+    ///     let decoded = morse.decode_timings(&timings);
+    ///     assert_eq!(decoded.text, "ANA");
+    ///     assert!(decoded.characters[0].confidence > 0.95);
+    ///
+    ///     let doubtful = decoded.doubtful_characters(0.9);
+    ///     assert_eq!(doubtful.len(), 1);
+    ///     assert_eq!(doubtful[0].code, ".-");
+    ///     assert_eq!(doubtful[0].marks[1], ms(190));
+    ///     let alternative = doubtful[0].alternative.as_ref().unwrap();
+    ///     assert_eq!(alternative.text, "I");
+    ///     assert!(alternative.confidence < doubtful[0].confidence);
+    /// }
+    /// ```
+    pub fn doubtful_characters(&self, threshold: f32) -> Vec<&DecodedCharacter> {
+        self.characters
+            .iter()
+            .filter(|character| character.confidence < threshold)
+            .collect()
+    }
+
+    /// Retourne l'évolution de la vitesse estimée : pour chaque caractère décodé (hors
+    /// séparations de mots), l'instant de son début et la vitesse en mots par minute.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Morse;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     // Un opérateur qui ralentit de 20 à 12 mots par minute
+    ///     let mut morse = Morse::new();
+    ///     morse.set_sampling_rate(8000);
+    ///     let mut samples: Vec<f32> = Vec::new();
+    ///     for dit in [60, 80, 100] {
+    ///         let dit: Duration = Duration::from_millis(dit);
+    ///         morse.set_short_beep(600.0, dit, 0.2);
+    ///         morse.set_long_beep(600.0, 3 * dit, 0.2);
+    ///         morse.set_silence(0.0, 2 * dit, 0.0);
+    ///         morse.set_duration(dit);
+    ///         samples.extend(morse.text_source("PARIS PARIS "));
+    ///     }
+    ///
+    ///     let decoded = Morse::new().decode_samples(&samples, 8000);
+    ///     assert_eq!(decoded.text, "PARIS PARIS PARIS PARIS PARIS PARIS");
+    ///     let speed: Vec<(Duration, f32)> = decoded.wpm_over_time();
+    ///     assert!((speed[0].1 - 20.0).abs() < 2.0);
+    ///     assert!((speed[speed.len() - 1].1 - 12.0).abs() < 1.0);
+    /// }
+    /// ```
+    pub fn wpm_over_time(&self) -> Vec<(Duration, f32)> {
+        self.characters
+            .iter()
+            .filter(|character| character.code != "/")
+            .map(|character| (character.start, character.wpm))
+            .collect()
+    }
+}
+
+/// Vitesses acceptées par `wpm_to_dit`, en mots par minute.
+pub(crate) const WPM_RANGE: (f32, f32) = (1.0, 200.0);
+
+/// Convertit une vitesse en mots par minute en durée d'un point, arrondie à la nanoseconde.
+/// La vitesse est ramenée dans `WPM_RANGE` (une vitesse NaN vaut le minimum).
+pub(crate) fn wpm_to_dit(wpm: f32) -> Duration {
+    let wpm: f32 = wpm.max(WPM_RANGE.0).min(WPM_RANGE.1);
+    Duration::from_nanos((1.2e9 / wpm as f64).round() as u64)
+}
+
+/// Convertit la durée d'un point en vitesse en mots par minute.
+pub(crate) fn dit_to_wpm(dit: Duration) -> f32 {
+    if dit.is_zero() {
+        return 0.0;
+    }
+    1.2 / dit.as_secs_f32()
+}
+
+/// Estime la durée d'un signal court, celle d'un signal long et celle du silence entre les
+/// signaux d'un caractère (relativement à un signal court) à partir du début d'une suite de
+/// durées de manipulation (`true` pour un signal, `false` pour un silence).
+///
+/// Les signaux sont répartis en deux groupes (points et tirets) par la méthode des k-moyennes.
+/// Un point suivi d'un silence entre deux signaux dure deux points quelle que soit la
+/// pondération : les plus courtes de ces paires donnent le silence entre les signaux, qui peut
+/// être plus court ou plus long qu'un point. Si tous les signaux ont la même longueur, le plus
+/// court silence entre deux signaux, qui dure un point, permet de savoir s'il s'agit de points
+/// ou de tirets.
+pub(crate) fn estimate_speed(runs: &[(bool, Duration)]) -> Option<(Duration, Duration, f64)> {
+    let first: usize = runs.iter().position(|(key_down, _)| *key_down)?;
+    let mut marks: Vec<f64> = Vec::new();
+    let mut pairs: Vec<(f64, f64)> = Vec::new();
+    let mut shortest_gap: Option<f64> = None;
+    for (index, &(key_down, duration)) in runs.iter().enumerate().skip(first) {
+        if marks.len() == SpeedModel::WARM_UP {
+            break;
+        }
+        let duration: f64 = duration.as_secs_f64();
+        if key_down {
+            marks.push(duration);
+        } else if runs[index + 1..].iter().any(|(key_down, _)| *key_down) {
+            shortest_gap = Some(shortest_gap.map_or(duration, |gap| gap.min(duration)));
+            pairs.push((marks[marks.len() - 1], duration));
+        }
+    }
+
+    let (short, long) = two_means(&marks);
+    if long > 1.5 * short {
+        // Paires dont le signal est un point, et parmi elles celles qui durent deux points
+        // (les suivantes en durent au moins quatre)
+        let threshold: f64 = (short * long).sqrt();
+        let dit_pairs: Vec<(f64, f64)> = pairs
+            .into_iter()
+            .filter(|(mark, _)| *mark <= threshold)
+            .collect();
+        let shortest: f64 = dit_pairs
+            .iter()
+            .map(|(mark, gap)| mark + gap)
+            .fold(f64::INFINITY, f64::min);
+        let element_gaps: Vec<f64> = dit_pairs
+            .iter()
+            .filter(|(mark, gap)| mark + gap <= 1.5 * shortest)
+            .map(|(_, gap)| *gap)
+            .collect();
+        let element_gap: f64 = if element_gaps.is_empty() {
+            1.0
+        } else {
+            element_gaps.iter().sum::<f64>() / element_gaps.len() as f64 / short
+        };
+        return Some((
+            Duration::from_secs_f64(short),
+            Duration::from_secs_f64(long),
+            element_gap,
+        ));
+    }
+    let dit: f64 = match shortest_gap {
+        Some(gap) if gap < short / 2.0 => short,
+        Some(gap) => gap.min(short),
+        None => short,
+    };
+    Some((
+        Duration::from_secs_f64(dit),
+        Duration::from_secs_f64(3.0 * dit),
+        1.0,
+    ))
+}
+
+/// Décode une suite complète de durées de manipulation (`true` pour un signal, `false` pour un
+/// silence) et retourne les caractères reçus ainsi que la durée estimée d'un point à la fin.
+///
+/// La vitesse initiale est estimée sur les premiers signaux (voir `estimate_speed`), puis
+/// suivie au fil du message par un `SpeedModel`. Le silence après le dernier signal est ignoré.
+///
+/// `gaps` donne les durées attendues des silences entre les caractères et entre les mots, en
+/// points (voir `TimingTracker::set_gaps`).
+pub(crate) fn decode_runs(
+    characters: HashMap<String, char>,
+    gaps: (f64, f64),
+    runs: &[(bool, Duration)],
+) -> (Vec<DecodedCharacter>, Duration) {
+    let Some((dit, dash, element_gap)) = estimate_speed(runs) else {
+        return (Vec::new(), Duration::ZERO);
+    };
+    let last: usize = runs
+        .iter()
+        .rposition(|(key_down, _)| *key_down)
+        .unwrap_or(0);
+    let mut tracker: TimingTracker = TimingTracker::new(characters, dit, dash);
+    tracker.set_element_gap(element_gap);
+    tracker.set_gaps(gaps.0, gaps.1);
+    let mut decoded: Vec<DecodedCharacter> = Vec::new();
+    for &(key_down, duration) in &runs[..=last] {
+        decoded.extend(tracker.update(key_down, duration));
+    }
+    decoded.extend(tracker.finish());
+    (decoded, tracker.dit())
+}
+
+/// Reconstitue le code Morse de caractères décodés, avec un espace entre les caractères et
+/// '/' entre les mots.
+pub(crate) fn characters_to_morse(characters: &[DecodedCharacter]) -> String {
+    characters
+        .iter()
+        .map(|character| character.code.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Répartit des valeurs en deux groupes et retourne le centre de chaque groupe.
+fn two_means(values: &[f64]) -> (f64, f64) {
+    let mut low: f64 = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut high: f64 = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    for _ in 0..16 {
+        let threshold: f64 = (low + high) / 2.0;
+        let (lows, highs): (Vec<f64>, Vec<f64>) =
+            values.iter().partition(|value| **value <= threshold);
+        if !lows.is_empty() {
+            low = lows.iter().sum::<f64>() / lows.len() as f64;
+        }
+        if !highs.is_empty() {
+            high = highs.iter().sum::<f64>() / highs.len() as f64;
+        }
+    }
+    (low, high)
+}
+
+/// Un caractère décodé au fil de l'eau, avec l'intervalle de temps pendant lequel il a été reçu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedCharacter {
+    /// Le caractère, s'il est connu de l'alphabet (' ' pour une séparation de mots).
+    pub character: Option<char>,
+    /// Le code Morse reçu ('/' pour une séparation de mots).
+    pub code: String,
+    /// Instant du début du premier signal du caractère.
+    pub start: Duration,
+    /// Instant de la fin du dernier signal du caractère.
+    pub end: Duration,
+    /// Vitesse estimée en mots par minute au moment où le caractère a été décodé.
+    pub wpm: f32,
+    /// Confiance dans le décodage du caractère, entre 0.0 et 1.0 : le produit des certitudes du
+    /// classement de chaque signal et de chaque silence du caractère. Les séparations de mots
+    /// ont une confiance de 1.0.
+    pub confidence: f32,
+    /// Durées des signaux reçus, dans l'ordre.
+    pub marks: Vec<Duration>,
+    /// Durées des silences entre les signaux du caractère.
+    pub gaps: Vec<Duration>,
+    /// Interprétation la plus probable après celle retenue, si elle correspond à des caractères
+    /// connus de l'alphabet.
+    pub alternative: Option<Alternative>,
+}
+
+/// Autre interprétation possible d'un caractère décodé : un signal classé autrement (point au
+/// lieu d'un tiret ou l'inverse), ou un silence qui sépare en fait deux caractères.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alternative {
+    /// Le texte de cette interprétation (un ou deux caractères).
+    pub text: String,
+    /// Le code Morse correspondant, avec un espace entre les caractères s'il y en a deux.
+    pub code: String,
+    /// Confiance dans cette interprétation, entre 0.0 et 1.0.
+    pub confidence: f32,
+}
+
+/// Modèle adaptatif de la manipulation, qui suit les changements de vitesse et de pondération
+/// d'un opérateur.
+///
+/// Le modèle retient la durée d'un signal court et, relativement à celle-ci, la durée moyenne
+/// d'un tiret et celles des trois sortes de silences (entre les signaux d'un caractère, entre
+/// les caractères et entre les mots). Sans pondération, le signal court et le silence entre
+/// deux signaux durent un point ; avec une pondération, l'un est allongé et l'autre raccourci
+/// d'autant. Chaque durée reçue est attribuée à la classe la plus proche
+/// (seuil à la moyenne géométrique de deux classes voisines), puis la classe et la durée d'un
+/// point sont rapprochées de la valeur observée, à la manière de k-moyennes glissantes.
+#[derive(Debug, Clone)]
+pub(crate) struct SpeedModel {
+    dit: f64,
+    dash_ratio: f64,
+    element_gap: f64,
+    character_gap: f64,
+    word_gap: f64,
+    observations: usize,
+}
+
+impl SpeedModel {
+    /// Nombre de signaux utilisés pour estimer la vitesse initiale d'un message complet.
+    pub(crate) const WARM_UP: usize = 16;
+    /// Poids minimal d'une nouvelle observation dans la durée d'un point.
+    const SPEED_RATE: f64 = 0.2;
+    /// Poids d'une nouvelle observation dans les durées relatives des classes.
+    const RATIO_RATE: f64 = 0.1;
+    /// Durées relatives acceptées pour le silence entre les signaux d'un caractère (pondération
+    /// d'environ 20 % à 80 %).
+    const ELEMENT_GAP_RANGE: (f64, f64) = (0.25, 4.0);
+    /// Pente de la fonction logistique qui convertit l'écart au seuil en certitude : une durée
+    /// exactement au centre de sa classe a une certitude d'environ 0.99.
+    const SHARPNESS: f64 = 10.0;
+
+    /// Crée un modèle à partir des durées attendues d'un point et d'un tiret, avec les silences
+    /// standards (1, 3 et 7 points).
+    pub(crate) fn new(dit: Duration, dash: Duration) -> Self {
+        let dit: f64 = dit.as_secs_f64();
+        SpeedModel {
+            dit,
+            dash_ratio: if dit > 0.0 {
+                (dash.as_secs_f64() / dit).max(1.5)
+            } else {
+                3.0
+            },
+            element_gap: 1.0,
+            character_gap: 3.0,
+            word_gap: 7.0,
+            observations: 0,
+        }
+    }
+
+    /// Remplace la durée attendue du silence entre les signaux d'un caractère, relativement à
+    /// un signal court, pour un message pondéré. Les silences entre les caractères et entre les
+    /// mots redeviennent les silences standards (3 et 7 points), raccourcis de la même façon.
+    pub(crate) fn set_element_gap(&mut self, element_gap: f64) {
+        self.element_gap = element_gap.clamp(Self::ELEMENT_GAP_RANGE.0, Self::ELEMENT_GAP_RANGE.1);
+        self.set_gaps(3.0, 7.0);
+    }
+
+    /// Remplace les durées attendues des silences entre les caractères et entre les mots, en
+    /// points, par exemple pour un message manipulé avec un espacement Farnsworth.
+    ///
+    /// Avec une pondération (voir `set_element_gap`), ces silences sont raccourcis de
+    /// l'allongement d'un signal court, comme le silence entre les signaux.
+    pub(crate) fn set_gaps(&mut self, character_gap: f64, word_gap: f64) {
+        let points: f64 = self.points();
+        self.character_gap = ((character_gap + 1.0) * points - 1.0).max(1.5 * self.element_gap);
+        self.word_gap = ((word_gap + 1.0) * points - 1.0).max(1.5 * self.character_gap);
+    }
+
+    /// Retourne la durée d'un point relativement à un signal court : la moitié d'un signal court
+    /// suivi d'un silence entre deux signaux.
+    fn points(&self) -> f64 {
+        (1.0 + self.element_gap) / 2.0
+    }
+
+    /// Retourne la durée estimée d'un point, quelle que soit la pondération.
+    pub(crate) fn dit(&self) -> Duration {
+        Duration::from_secs_f64(self.dit * self.points())
+    }
+
+    /// Retourne la vitesse estimée en mots par minute.
+    pub(crate) fn wpm(&self) -> f32 {
+        dit_to_wpm(self.dit())
+    }
+
+    /// Indique si un signal de cette durée est un tiret.
+    pub(crate) fn is_dash(&self, mark: Duration) -> bool {
+        mark.as_secs_f64() > self.dit * self.dash_ratio.sqrt()
+    }
+
+    /// Indique si un silence de cette durée sépare deux caractères (ou deux mots).
+    pub(crate) fn ends_character(&self, gap: Duration) -> bool {
+        gap.as_secs_f64() >= self.dit * (self.element_gap * self.character_gap).sqrt()
+    }
+
+    /// Indique si un silence de cette durée sépare deux mots.
+    pub(crate) fn ends_word(&self, gap: Duration) -> bool {
+        gap.as_secs_f64() >= self.dit * (self.character_gap * self.word_gap).sqrt()
+    }
+
+    /// Retourne la certitude (entre 0.5 et 1.0) du classement d'un signal en point ou en tiret.
+    pub(crate) fn mark_certainty(&self, mark: Duration) -> f32 {
+        let position: f64 = (mark.as_secs_f64() / self.dit).ln() / self.dash_ratio.ln();
+        Self::certainty(position)
+    }
+
+    /// Retourne la certitude (entre 0.5 et 1.0) du classement d'un silence en séparation des
+    /// signaux d'un caractère plutôt qu'en séparation de caractères.
+    pub(crate) fn gap_certainty(&self, gap: Duration) -> f32 {
+        let position: f64 = (gap.as_secs_f64() / (self.dit * self.element_gap)).ln()
+            / (self.character_gap / self.element_gap).ln();
+        Self::certainty(position)
+    }
+
+    /// Convertit la position d'une durée entre deux classes (0.0 pour la première, 1.0 pour la
+    /// seconde, mesurée sur une échelle logarithmique) en certitude, avec une fonction
+    /// logistique de l'écart au seuil.
+    fn certainty(position: f64) -> f32 {
+        let margin: f64 = (position - 0.5).abs();
+        (1.0 / (1.0 + (-Self::SHARPNESS * margin).exp())) as f32
+    }
+
+    /// Classe un signal, met à jour le modèle et indique s'il s'agit d'un tiret.
+    pub(crate) fn observe_mark(&mut self, mark: Duration) -> bool {
+        let dash: bool = self.is_dash(mark);
+        let mark: f64 = mark.as_secs_f64();
+        if dash {
+            self.follow_speed(mark / self.dash_ratio);
+            self.dash_ratio = Self::approach(self.dash_ratio, mark / self.dit).max(1.5);
+        } else {
+            self.follow_speed(mark);
+        }
+        dash
+    }
+
+    /// Met à jour le modèle avec un silence compris entre deux signaux.
+    ///
+    /// Les silences beaucoup plus longs qu'une séparation de mots (pause de l'opérateur) sont
+    /// ignorés.
+    pub(crate) fn observe_gap(&mut self, gap: Duration) {
+        if self.observations == 0 {
+            return;
+        }
+        let units: f64 = gap.as_secs_f64() / self.dit;
+        if !self.ends_character(gap) {
+            self.follow_speed(gap.as_secs_f64() / self.element_gap);
+            self.element_gap = Self::approach(self.element_gap, units)
+                .clamp(Self::ELEMENT_GAP_RANGE.0, Self::ELEMENT_GAP_RANGE.1);
+        } else if !self.ends_word(gap) {
+            self.character_gap = Self::approach(self.character_gap, units);
+        } else if units < 2.0 * self.word_gap {
+            self.word_gap = Self::approach(self.word_gap, units);
+        }
+        self.character_gap = self.character_gap.max(1.5 * self.element_gap);
+        self.word_gap = self.word_gap.max(1.5 * self.character_gap);
+    }
+
+    /// Rapproche la durée d'un point d'une nouvelle estimation. Les premières observations ont
+    /// plus de poids, pour converger rapidement quand la vitesse attendue est fausse.
+    fn follow_speed(&mut self, dit: f64) {
+        self.observations += 1;
+        let rate: f64 = (1.0 / self.observations as f64).max(Self::SPEED_RATE);
+        self.dit += rate * (dit - self.dit);
+    }
+
+    /// Rapproche une durée relative d'une observation.
+    fn approach(ratio: f64, observed: f64) -> f64 {
+        ratio + Self::RATIO_RATE * (observed - ratio)
+    }
+}
+
+/// Décodeur incrémental de durées de manipulation.
+///
+/// Il reçoit l'état de la clé (signal ou silence) et le temps écoulé dans cet état, et émet
+/// chaque caractère dès que le silence qui le suit est assez long, sans attendre le signal
+/// suivant : le délai de décodage est ainsi limité à environ 2 points. Les signaux et les
+/// silences sont classés par un `SpeedModel`, qui suit les changements de vitesse.
+#[derive(Debug, Clone)]
+pub(crate) struct TimingTracker {
+    characters: HashMap<String, char>,
+    model: SpeedModel,
+    time: Duration,
+    key_down: bool,
+    run_start: Duration,
+    code: String,
+    marks: Vec<Duration>,
+    gaps: Vec<Duration>,
+    mark_certainties: Vec<f32>,
+    gap_certainties: Vec<f32>,
+    character_start: Duration,
+    character_end: Duration,
+    word_pending: bool,
+}
+
+impl TimingTracker {
+    /// Crée un décodeur à partir de l'alphabet et des durées attendues d'un point et d'un tiret.
+    pub(crate) fn new(characters: HashMap<String, char>, dit: Duration, dash: Duration) -> Self {
+        TimingTracker {
+            characters,
+            model: SpeedModel::new(dit, dash),
+            time: Duration::ZERO,
+            key_down: false,
+            run_start: Duration::ZERO,
+            code: String::new(),
+            marks: Vec::new(),
+            gaps: Vec::new(),
+            mark_certainties: Vec::new(),
+            gap_certainties: Vec::new(),
+            character_start: Duration::ZERO,
+            character_end: Duration::ZERO,
+            word_pending: false,
+        }
+    }
+
+    /// Remplace la durée attendue du silence entre les signaux d'un caractère, relativement à
+    /// un signal court (voir `SpeedModel::set_element_gap`).
+    pub(crate) fn set_element_gap(&mut self, element_gap: f64) {
+        self.model.set_element_gap(element_gap);
+    }
+
+    /// Remplace les durées attendues des silences entre les caractères et entre les mots, en
+    /// points.
+    pub(crate) fn set_gaps(&mut self, character_gap: f64, word_gap: f64) {
+        self.model.set_gaps(character_gap, word_gap);
+    }
+
+    /// Retourne la durée estimée d'un point.
+    pub(crate) fn dit(&self) -> Duration {
+        self.model.dit()
+    }
+
+    /// Retourne le temps total reçu.
+    pub(crate) fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Indique si la clé est actuellement enfoncée.
+    pub(crate) fn key_down(&self) -> bool {
+        self.key_down
+    }
+
+    /// Indique que la clé est restée dans l'état `key_down` pendant `elapsed`, et retourne les
+    /// caractères qui ont pu être décodés.
+    pub(crate) fn update(&mut self, key_down: bool, elapsed: Duration) -> Vec<DecodedCharacter> {
+        let mut decoded: Vec<DecodedCharacter> = Vec::new();
+        if key_down != self.key_down {
+            self.end_run(&mut decoded);
+            self.key_down = key_down;
+            self.run_start = self.time;
+        }
+        self.time += elapsed;
+        if !self.key_down {
+            self.flush_gap(&mut decoded);
+        }
+        decoded
+    }
+
+    /// Termine le message en cours et retourne le dernier caractère, s'il y en a un.
+    pub(crate) fn finish(&mut self) -> Vec<DecodedCharacter> {
+        let mut decoded: Vec<DecodedCharacter> = Vec::new();
+        if self.key_down {
+            self.end_run(&mut decoded);
+            self.key_down = false;
+            self.run_start = self.time;
+        }
+        self.flush_character(&mut decoded);
+        decoded
+    }
+
+    /// Traite la fin d'un signal ou d'un silence.
+    fn end_run(&mut self, decoded: &mut Vec<DecodedCharacter>) {
+        let duration: Duration = self.time - self.run_start;
+        if duration.is_zero() {
+            return;
+        }
+        if self.key_down {
+            if self.code.is_empty() {
+                self.character_start = self.run_start;
+            }
+            self.mark_certainties
+                .push(self.model.mark_certainty(duration));
+            self.marks.push(duration);
+            let dash: bool = self.model.observe_mark(duration);
+            self.code.push(if dash { '-' } else { '.' });
+            self.character_end = self.time;
+        } else {
+            self.flush_gap(decoded);
+            if !self.code.is_empty() {
+                self.gap_certainties
+                    .push(self.model.gap_certainty(duration));
+                self.gaps.push(duration);
+            }
+            self.model.observe_gap(duration);
+        }
+    }
+
+    /// Émet le caractère et la séparation de mots dès que le silence en cours est assez long.
+    fn flush_gap(&mut self, decoded: &mut Vec<DecodedCharacter>) {
+        let gap: Duration = self.time - self.run_start;
+        if self.model.ends_character(gap) {
+            self.flush_character(decoded);
+        }
+        if self.model.ends_word(gap) && self.word_pending {
+            self.word_pending = false;
+            decoded.push(DecodedCharacter {
+                character: Some(' '),
+                code: "/".to_string(),
+                start: self.run_start,
+                end: self.run_start,
+                wpm: self.model.wpm(),
+                confidence: 1.0,
+                marks: Vec::new(),
+                gaps: Vec::new(),
+                alternative: None,
+            });
+        }
+    }
+
+    /// Émet le caractère en cours, s'il y en a un.
+    fn flush_character(&mut self, decoded: &mut Vec<DecodedCharacter>) {
+        if self.code.is_empty() {
+            return;
+        }
+        let code: String = std::mem::take(&mut self.code);
+        let mark_certainties: Vec<f32> = std::mem::take(&mut self.mark_certainties);
+        let gap_certainties: Vec<f32> = std::mem::take(&mut self.gap_certainties);
+        let confidence: f32 = mark_certainties.iter().chain(&gap_certainties).product();
+        decoded.push(DecodedCharacter {
+            character: self.characters.get(&code).copied(),
+            alternative: self.alternative(&code, confidence, &mark_certainties, &gap_certainties),
+            code,
+            start: self.character_start,
+            end: self.character_end,
+            wpm: self.model.wpm(),
+            confidence,
+            marks: std::mem::take(&mut self.marks),
+            gaps: std::mem::take(&mut self.gaps),
+        });
+        self.word_pending = true;
+    }
+
+    /// Recherche l'interprétation la plus probable d'un caractère après celle retenue, en
+    /// changeant le classement d'un seul signal ou d'un seul silence.
+    fn alternative(
+        &self,
+        code: &str,
+        confidence: f32,
+        mark_certainties: &[f32],
+        gap_certainties: &[f32],
+    ) -> Option<Alternative> {
+        let symbols: Vec<char> = code.chars().collect();
+        let mut candidates: Vec<(String, f32)> = Vec::new();
+        for (index, certainty) in mark_certainties.iter().enumerate() {
+            let mut flipped: Vec<char> = symbols.clone();
+            flipped[index] = if flipped[index] == '-' { '.' } else { '-' };
+            candidates.push((
+                flipped.into_iter().collect(),
+                confidence / certainty * (1.0 - certainty),
+            ));
+        }
+        for (index, certainty) in gap_certainties.iter().enumerate() {
+            let (first, second) = symbols.split_at(index + 1);
+            candidates.push((
+                format!(
+                    "{} {}",
+                    first.iter().collect::<String>(),
+                    second.iter().collect::<String>()
+                ),
+                confidence / certainty * (1.0 - certainty),
+            ));
+        }
+        candidates
+            .into_iter()
+            .filter_map(|(code, confidence)| {
+                let text: Option<String> = code
+                    .split(' ')
+                    .map(|code| self.characters.get(code).copied())
+                    .collect();
+                Some(Alternative {
+                    text: text?,
+                    code,
+                    confidence,
+                })
+            })
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+    }
+}
+
+/// Décodeur incrémental de durées de manipulation, pour les manipulateurs et les claviers qui
+/// fournissent l'état de la clé plutôt que de l'audio.
+///
+/// Les durées peuvent être fournies directement (`push`) ou sous forme d'instants de
+/// changement d'état de la clé (`key_event`), mesurés depuis le début du message. Le
+/// classement des signaux et des silences est le même que celui du décodeur audio.
+#[derive(Debug, Clone)]
+pub struct StreamingTimingDecoder {
+    tracker: TimingTracker,
+}
+
+impl StreamingTimingDecoder {
+    pub(crate) fn new(tracker: TimingTracker) -> Self {
+        StreamingTimingDecoder { tracker }
+    }
+
+    /// Indique que la clé est restée dans l'état `key_down` pendant `duration`, et retourne les
+    /// caractères décodés depuis le dernier appel.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_down` - `true` si la clé était enfoncée (signal), `false` sinon (silence).
+    /// * `duration` - Le temps passé dans cet état.
+    pub fn push(&mut self, key_down: bool, duration: Duration) -> Vec<DecodedCharacter> {
+        self.tracker.update(key_down, duration)
+    }
+
+    /// Indique que la clé a changé d'état à l'instant `at`, et retourne les caractères décodés
+    /// depuis le dernier appel.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_down` - Le nouvel état de la clé : `true` si elle vient d'être enfoncée.
+    /// * `at` - L'instant du changement, depuis le début du message. Un instant antérieur au
+    ///   précédent est traité comme simultané.
+    pub fn key_event(&mut self, key_down: bool, at: Duration) -> Vec<DecodedCharacter> {
+        let mut decoded: Vec<DecodedCharacter> = self.tick(at);
+        decoded.extend(self.tracker.update(key_down, Duration::ZERO));
+        decoded
+    }
+
+    /// Fait avancer le temps jusqu'à l'instant `at` sans changement d'état de la clé, et
+    /// retourne les caractères décodés depuis le dernier appel.
+    ///
+    /// À appeler régulièrement entre deux événements, pour que le dernier caractère soit émis
+    /// dès que le silence qui le suit est assez long.
+    pub fn tick(&mut self, at: Duration) -> Vec<DecodedCharacter> {
+        let elapsed: Duration = at.saturating_sub(self.tracker.time());
+        self.tracker.update(self.tracker.key_down(), elapsed)
+    }
+
+    /// Termine le message et retourne les derniers caractères décodés.
+    pub fn finish(&mut self) -> Vec<DecodedCharacter> {
+        self.tracker.finish()
+    }
+
+    /// Retourne la durée estimée d'un point.
+    pub fn dit(&self) -> Duration {
+        self.tracker.dit()
+    }
+
+    /// Retourne la vitesse estimée en mots par minute.
+    pub fn wpm(&self) -> f32 {
+        dit_to_wpm(self.tracker.dit())
+    }
+
+    /// Retourne le temps écoulé depuis le début du message.
+    pub fn time(&self) -> Duration {
+        self.tracker.time()
+    }
+}
@@ -0,0 +1,695 @@
+use hound::Error as HoundError;
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    audio::ExportReport,
+    json::Value,
+    morse::Morse,
+    rng::Rng,
+    scoring::{score_copy, Confusion, CopyScore},
+};
+
+/// Ordre standard d'apprentissage des caractères de la méthode Koch (celui de LCWO).
+pub const KOCH_ORDER: [char; 41] = [
+    'K', 'M', 'U', 'R', 'E', 'S', 'N', 'A', 'P', 'T', 'L', 'W', 'I', '.', 'J', 'Z', '=', 'F', 'O',
+    'Y', ',', 'V', 'G', '5', '/', 'Q', '9', '2', 'H', '3', '8', 'B', '?', '4', '7', 'C', '1', 'D',
+    '6', '0', 'X',
+];
+
+/// Générateur de leçons selon la méthode Koch.
+///
+/// Une leçon de niveau N utilise les N premiers caractères de `KOCH_ORDER`. Les caractères
+/// sont manipulés à pleine vitesse et les silences allongés (espacement Farnsworth) : l'élève
+/// apprend le rythme de chaque caractère plutôt que de compter les points et les tirets. Les
+/// groupes sont tirés au hasard, le dernier caractère appris étant tiré plus souvent que les
+/// autres.
+#[derive(Debug, Clone)]
+pub struct Koch {
+    level: usize,
+    group_length: usize,
+    group_count: usize,
+    newest_weight: f32,
+    character_wpm: f32,
+    effective_wpm: f32,
+    seed: u64,
+}
+
+impl Koch {
+    /// Crée un générateur de leçons pour un niveau donné, avec des groupes de 5 caractères,
+    /// 20 groupes par leçon, des caractères à 20 mots par minute et une vitesse effective de
+    /// 10 mots par minute.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Le nombre de caractères appris, ramené entre 2 et 41.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::Koch;
+    ///
+    /// fn main() {
+    ///     let koch = Koch::new(4);
+    ///     assert_eq!(koch.characters(), vec!['K', 'M', 'U', 'R']);
+    ///     assert_eq!(koch.newest_character(), 'R');
+    /// }
+    /// ```
+    pub fn new(level: usize) -> Self {
+        let seed: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or(0);
+        Koch {
+            level: level.clamp(2, KOCH_ORDER.len()),
+            group_length: 5,
+            group_count: 20,
+            newest_weight: 2.0,
+            character_wpm: 20.0,
+            effective_wpm: 10.0,
+            seed,
+        }
+    }
+
+    /// Retourne le niveau, c'est-à-dire le nombre de caractères appris.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Retourne les caractères utilisés à ce niveau, dans l'ordre d'apprentissage.
+    pub fn characters(&self) -> Vec<char> {
+        KOCH_ORDER[..self.level].to_vec()
+    }
+
+    /// Retourne le dernier caractère appris, tiré plus souvent que les autres.
+    pub fn newest_character(&self) -> char {
+        KOCH_ORDER[self.level - 1]
+    }
+
+    /// Change le nombre de caractères de chaque groupe (au moins 1).
+    pub fn set_group_length(&mut self, group_length: usize) {
+        self.group_length = group_length.max(1);
+    }
+
+    /// Change le nombre de groupes d'une leçon.
+    pub fn set_group_count(&mut self, group_count: usize) {
+        self.group_count = group_count;
+    }
+
+    /// Change le poids du dernier caractère appris par rapport aux autres caractères (1.0 pour
+    /// un tirage uniforme).
+    pub fn set_newest_weight(&mut self, newest_weight: f32) {
+        self.newest_weight = newest_weight.max(0.0);
+    }
+
+    /// Change la vitesse des caractères et la vitesse effective de la leçon, en mots par
+    /// minute (voir `Morse::set_farnsworth`).
+    pub fn set_speed(&mut self, character_wpm: f32, effective_wpm: f32) {
+        self.character_wpm = character_wpm;
+        self.effective_wpm = effective_wpm;
+    }
+
+    /// Change la graine du tirage, pour générer une leçon reproductible.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Génère une leçon.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Koch, Lesson};
+    ///
+    /// fn main() {
+    ///     let mut koch = Koch::new(5);
+    ///     koch.set_group_length(4);
+    ///     koch.set_group_count(50);
+    ///     koch.set_newest_weight(3.0);
+    ///     koch.set_seed(42);
+    ///     let lesson: Lesson = koch.lesson();
+    ///     assert_eq!(lesson.groups.len(), 50);
+    ///     assert!(lesson.groups.iter().all(|group| group.chars().count() == 4));
+    ///     assert!(lesson.text().chars().all(|c| c == ' ' || "KMURE".contains(c)));
+    ///
+    ///     // 'E', le dernier caractère appris, est tiré environ trois fois plus souvent
+    ///     let count = |character| lesson.text().matches(character).count();
+    ///     assert!(count('E') > 2 * count('K'));
+    ///
+    ///     // La même graine donne la même leçon
+    ///     assert_eq!(koch.lesson(), lesson);
+    /// }
+    /// ```
+    pub fn lesson(&self) -> Lesson {
+        let characters: Vec<char> = self.characters();
+        let weights: Vec<f32> = vec![1.0; characters.len()];
+        self.weighted_lesson(&weights)
+    }
+
+    /// Génère une leçon dont les caractères sont tirés selon les poids donnés (dans l'ordre de
+    /// `characters`), le poids du dernier caractère appris étant multiplié par `newest_weight`.
+    pub(crate) fn weighted_lesson(&self, weights: &[f32]) -> Lesson {
+        let characters: Vec<char> = self.characters();
+        let weights: Vec<f32> = weights
+            .iter()
+            .enumerate()
+            .map(|(index, weight)| {
+                if index + 1 == characters.len() {
+                    weight * self.newest_weight
+                } else {
+                    *weight
+                }
+            })
+            .collect();
+        let total: f32 = weights.iter().sum();
+        let mut rng: Rng = Rng::new(self.seed);
+        let groups: Vec<String> = (0..self.group_count)
+            .map(|_| {
+                (0..self.group_length)
+                    .map(|_| {
+                        let mut draw: f32 = rng.next_f32() * total;
+                        for (character, weight) in characters.iter().zip(&weights) {
+                            if draw < *weight {
+                                return *character;
+                            }
+                            draw -= weight;
+                        }
+                        characters[characters.len() - 1]
+                    })
+                    .collect()
+            })
+            .collect();
+        Lesson {
+            characters,
+            groups,
+            character_wpm: self.character_wpm,
+            effective_wpm: self.effective_wpm,
+        }
+    }
+}
+
+/// Leçon générée par `Koch::lesson` : des groupes de caractères à recopier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lesson {
+    /// Caractères utilisés dans la leçon.
+    pub characters: Vec<char>,
+    /// Groupes de caractères, dans l'ordre d'envoi.
+    pub groups: Vec<String>,
+    /// Vitesse des caractères, en mots par minute.
+    pub character_wpm: f32,
+    /// Vitesse effective de la leçon, en mots par minute.
+    pub effective_wpm: f32,
+}
+
+impl Lesson {
+    /// Retourne le texte de la leçon, les groupes étant séparés par des espaces.
+    pub fn text(&self) -> String {
+        self.groups.join(" ")
+    }
+
+    /// Retourne le code Morse de la leçon.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse` - L'alphabet utilisé pour encoder la leçon.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Koch, Morse};
+    ///
+    /// fn main() {
+    ///     let mut koch = Koch::new(2);
+    ///     koch.set_group_length(2);
+    ///     koch.set_group_count(2);
+    ///     let lesson = koch.lesson();
+    ///     let code: String = lesson.morse_code(&Morse::new());
+    ///     assert_eq!(Morse::new().decode(&code), lesson.text());
+    /// }
+    /// ```
+    pub fn morse_code(&self, morse: &Morse) -> String {
+        morse.encode(&self.text())
+    }
+
+    /// Exporte la leçon en fichier WAV, avec l'espacement Farnsworth de la leçon.
+    ///
+    /// Les tonalités, l'alphabet et les réglages d'export de `morse` sont conservés ; seules
+    /// les durées sont remplacées.
+    ///
+    /// # Arguments
+    ///
+    /// * `morse` - Les réglages utilisés pour le rendu audio.
+    /// * `filename` - Le nom du fichier WAV à créer.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Koch, Morse};
+    ///
+    /// fn main() {
+    ///     let mut koch = Koch::new(10);
+    ///     koch.set_speed(25.0, 12.0);
+    ///     koch.lesson().to_audio(&Morse::new(), "lecon.wav").unwrap();
+    ///     std::fs::remove_file("lecon.wav").unwrap();
+    /// }
+    /// ```
+    pub fn to_audio(&self, morse: &Morse, filename: &str) -> Result<ExportReport, HoundError> {
+        let mut morse: Morse = morse.clone();
+        morse.set_farnsworth(self.character_wpm, self.effective_wpm);
+        morse.to_audio(&self.morse_code(&morse), filename)
+    }
+}
+
+/// Progression d'un élève sur un caractère.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CharacterProgress {
+    /// Nombre total d'envois du caractère.
+    pub sent: usize,
+    /// Nombre total d'envois mal recopiés ou manqués.
+    pub errors: usize,
+    /// Précision récente, entre 0.0 et 1.0 : moyenne glissante des séances, qui donne plus de
+    /// poids aux dernières.
+    pub accuracy: f32,
+    /// Temps de réponse récent (moyenne glissante), s'il a été mesuré.
+    pub response_time: Option<Duration>,
+}
+
+/// Erreur de lecture ou d'écriture de la progression d'un élève.
+#[derive(Debug)]
+pub enum ProgressError {
+    /// Le fichier n'a pas pu être lu ou écrit.
+    Io(io::Error),
+    /// Le contenu n'est pas une progression valide.
+    Format(String),
+}
+
+impl fmt::Display for ProgressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgressError::Io(error) => write!(f, "impossible de lire ou d'écrire : {}", error),
+            ProgressError::Format(message) => write!(f, "progression invalide : {}", message),
+        }
+    }
+}
+
+impl Error for ProgressError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProgressError::Io(error) => Some(error),
+            ProgressError::Format(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ProgressError {
+    fn from(error: io::Error) -> Self {
+        ProgressError::Io(error)
+    }
+}
+
+/// Entraîneur adaptatif : il suit la précision et le temps de réponse d'un élève pour chaque
+/// caractère, d'une séance à l'autre, et génère des leçons Koch qui insistent sur ses points
+/// faibles.
+///
+/// Le poids de chaque caractère dans la leçon suivante augmente avec son taux d'erreur
+/// récent, avec sa lenteur par rapport aux autres caractères et avec la fréquence à laquelle
+/// il est recopié à la place d'un autre (les deux caractères d'une confusion reviennent donc
+/// plus souvent). Lorsque la précision d'une séance atteint le seuil (90 % par défaut), le
+/// niveau Koch augmente d'un caractère. La progression s'enregistre en JSON.
+#[derive(Debug, Clone)]
+pub struct Trainer {
+    koch: Koch,
+    threshold: f32,
+    sessions: usize,
+    progress: BTreeMap<char, CharacterProgress>,
+    confusions: BTreeMap<(char, char), usize>,
+}
+
+impl Trainer {
+    /// Poids d'une nouvelle séance dans la précision et d'une nouvelle mesure dans le temps
+    /// de réponse.
+    const RATE: f32 = 0.3;
+    /// Poids supplémentaire d'un caractère jamais réussi.
+    const WEAKNESS_WEIGHT: f32 = 3.0;
+    /// Poids supplémentaire maximal d'un caractère lent.
+    const SLOWNESS_WEIGHT: f32 = 2.0;
+    /// Poids supplémentaire d'un caractère recopié à la place d'un autre à chaque envoi.
+    const CONFUSION_WEIGHT: f32 = 2.0;
+
+    /// Crée un entraîneur pour un nouvel élève, à partir des réglages d'un générateur Koch.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Koch, Trainer};
+    ///
+    /// fn main() {
+    ///     let mut koch = Koch::new(2);
+    ///     koch.set_seed(7);
+    ///     let mut trainer = Trainer::new(koch);
+    ///
+    ///     // L'élève recopie mal les 'M'
+    ///     let lesson = trainer.next_lesson();
+    ///     let copied: String = lesson.text().replace('M', "N");
+    ///     let score = trainer.record_copy(&lesson, &copied);
+    ///     assert!(score.accuracy() < 0.9);
+    ///     assert_eq!(trainer.level(), 2);
+    ///     assert!(trainer.progress('M').unwrap().accuracy < trainer.progress('K').unwrap().accuracy);
+    ///
+    ///     // La leçon suivante insiste sur 'M'
+    ///     let lesson = trainer.next_lesson();
+    ///     let count = |character| lesson.text().matches(character).count();
+    ///     assert!(count('M') > 2 * count('K'));
+    ///
+    ///     // Une copie parfaite fait passer au niveau suivant
+    ///     trainer.record_copy(&lesson, &lesson.text());
+    ///     assert_eq!(trainer.level(), 3);
+    ///
+    ///     // La progression est enregistrée en JSON
+    ///     let restored = Trainer::from_json(&trainer.to_json()).unwrap();
+    ///     assert_eq!(restored.level(), 3);
+    ///     assert_eq!(restored.progress('M'), trainer.progress('M'));
+    /// }
+    /// ```
+    pub fn new(koch: Koch) -> Self {
+        Trainer {
+            koch,
+            threshold: 0.9,
+            sessions: 0,
+            progress: BTreeMap::new(),
+            confusions: BTreeMap::new(),
+        }
+    }
+
+    /// Retourne le niveau Koch actuel de l'élève.
+    pub fn level(&self) -> usize {
+        self.koch.level
+    }
+
+    /// Retourne le nombre de séances enregistrées.
+    pub fn sessions(&self) -> usize {
+        self.sessions
+    }
+
+    /// Change la précision à atteindre lors d'une séance pour passer au niveau suivant.
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold;
+    }
+
+    /// Retourne la progression de l'élève sur un caractère, s'il a déjà été envoyé ou chronométré.
+    pub fn progress(&self, character: char) -> Option<CharacterProgress> {
+        self.progress.get(&character).copied()
+    }
+
+    /// Retourne les confusions de l'élève sur toutes les séances, de la plus fréquente à la
+    /// moins fréquente.
+    pub fn confusions(&self) -> Vec<Confusion> {
+        let mut confusions: Vec<Confusion> = self
+            .confusions
+            .iter()
+            .map(|(&(sent, copied), &count)| Confusion {
+                sent,
+                copied,
+                count,
+            })
+            .collect();
+        confusions.sort_by_key(|confusion| Reverse(confusion.count));
+        confusions
+    }
+
+    /// Retourne le poids de chaque caractère du niveau actuel dans la prochaine leçon (avant
+    /// l'accent mis sur le dernier caractère appris).
+    pub fn weights(&self) -> Vec<(char, f32)> {
+        let characters: Vec<char> = self.koch.characters();
+        let mut times: Vec<f32> = characters
+            .iter()
+            .filter_map(|character| self.progress.get(character)?.response_time)
+            .map(|time| time.as_secs_f32())
+            .collect();
+        times.sort_by(f32::total_cmp);
+        let median: Option<f32> = times.get(times.len() / 2).copied();
+        characters
+            .iter()
+            .map(|&character| {
+                let mut weight: f32 = 1.0;
+                if let Some(progress) = self.progress.get(&character) {
+                    if progress.sent > 0 {
+                        weight += Self::WEAKNESS_WEIGHT * (1.0 - progress.accuracy);
+                    }
+                    if let (Some(time), Some(median)) = (progress.response_time, median) {
+                        if median > 0.0 {
+                            weight += (time.as_secs_f32() / median - 1.0)
+                                .clamp(0.0, Self::SLOWNESS_WEIGHT);
+                        }
+                    }
+                }
+                for (&(sent, copied), &count) in &self.confusions {
+                    let sent_count: usize = self.progress.get(&sent).map_or(0, |p| p.sent);
+                    if copied == character && sent_count > 0 {
+                        weight += Self::CONFUSION_WEIGHT * count as f32 / sent_count as f32;
+                    }
+                }
+                (character, weight)
+            })
+            .collect()
+    }
+
+    /// Génère la prochaine leçon, adaptée aux points faibles de l'élève. Chaque appel tire de
+    /// nouveaux groupes.
+    pub fn next_lesson(&mut self) -> Lesson {
+        let weights: Vec<f32> = self.weights().into_iter().map(|(_, w)| w).collect();
+        let lesson: Lesson = self.koch.weighted_lesson(&weights);
+        self.koch.seed = Rng::new(self.koch.seed).next_u64();
+        lesson
+    }
+
+    /// Enregistre la copie d'une leçon par l'élève et retourne son évaluation. Si la précision
+    /// atteint le seuil, le niveau Koch augmente d'un caractère.
+    pub fn record_copy(&mut self, lesson: &Lesson, copied: &str) -> CopyScore {
+        let score: CopyScore = score_copy(&lesson.text(), copied);
+        for (&character, result) in &score.characters {
+            let progress: &mut CharacterProgress = self.progress.entry(character).or_default();
+            let accuracy: f32 = 1.0 - result.error_rate();
+            progress.accuracy = if progress.sent == 0 {
+                accuracy
+            } else {
+                progress.accuracy + Self::RATE * (accuracy - progress.accuracy)
+            };
+            progress.sent += result.sent;
+            progress.errors += result.errors;
+        }
+        for confusion in &score.confusions {
+            *self
+                .confusions
+                .entry((confusion.sent, confusion.copied))
+                .or_insert(0) += confusion.count;
+        }
+        self.sessions += 1;
+        if score.sent > 0
+            && score.accuracy() >= self.threshold
+            && self.koch.level < KOCH_ORDER.len()
+        {
+            self.koch.level += 1;
+        }
+        score
+    }
+
+    /// Enregistre le temps mis par l'élève pour reconnaître un caractère.
+    pub fn record_response(&mut self, character: char, response_time: Duration) {
+        let progress: &mut CharacterProgress = self.progress.entry(character).or_default();
+        progress.response_time = Some(match progress.response_time {
+            None => response_time,
+            Some(previous) => Duration::from_secs_f32(
+                previous.as_secs_f32()
+                    + Self::RATE * (response_time.as_secs_f32() - previous.as_secs_f32()),
+            ),
+        });
+    }
+
+    /// Retourne la progression de l'élève et les réglages de ses leçons en JSON.
+    pub fn to_json(&self) -> String {
+        let number = |value: f64| Value::Number(value);
+        // Écrit un `f32` avec ses seuls chiffres significatifs (0.9 plutôt que 0.8999999761…)
+        let float = |value: f32| Value::Number(value.to_string().parse().unwrap_or(0.0));
+        let characters: Vec<(String, Value)> = self
+            .progress
+            .iter()
+            .map(|(character, progress)| {
+                let mut fields: Vec<(String, Value)> = vec![
+                    ("sent".to_string(), number(progress.sent as f64)),
+                    ("errors".to_string(), number(progress.errors as f64)),
+                    ("accuracy".to_string(), float(progress.accuracy)),
+                ];
+                if let Some(time) = progress.response_time {
+                    fields.push(("response_time".to_string(), number(time.as_secs_f64())));
+                }
+                (character.to_string(), Value::Object(fields))
+            })
+            .collect();
+        let confusions: Vec<Value> = self
+            .confusions
+            .iter()
+            .map(|(&(sent, copied), &count)| {
+                Value::Object(vec![
+                    ("sent".to_string(), Value::String(sent.to_string())),
+                    ("copied".to_string(), Value::String(copied.to_string())),
+                    ("count".to_string(), number(count as f64)),
+                ])
+            })
+            .collect();
+        Value::Object(vec![
+            ("level".to_string(), number(self.koch.level as f64)),
+            (
+                "group_length".to_string(),
+                number(self.koch.group_length as f64),
+            ),
+            (
+                "group_count".to_string(),
+                number(self.koch.group_count as f64),
+            ),
+            ("newest_weight".to_string(), float(self.koch.newest_weight)),
+            ("character_wpm".to_string(), float(self.koch.character_wpm)),
+            ("effective_wpm".to_string(), float(self.koch.effective_wpm)),
+            // Une graine sur 64 bits ne tient pas exactement dans un nombre JSON
+            (
+                "seed".to_string(),
+                Value::String(self.koch.seed.to_string()),
+            ),
+            ("threshold".to_string(), float(self.threshold)),
+            ("sessions".to_string(), number(self.sessions as f64)),
+            ("characters".to_string(), Value::Object(characters)),
+            ("confusions".to_string(), Value::Array(confusions)),
+        ])
+        .to_string()
+    }
+
+    /// Restaure un entraîneur à partir du JSON produit par `to_json`. Les champs absents
+    /// prennent leur valeur par défaut, sauf le niveau.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{ProgressError, Trainer};
+    ///
+    /// fn main() {
+    ///     let trainer = Trainer::from_json(r#"{"level":3,"characters":{"K":{"sent":4}}}"#);
+    ///     assert_eq!(trainer.unwrap().level(), 3);
+    ///
+    ///     // Un temps de réponse qui ne tient pas dans une durée est refusé
+    ///     let json = r#"{"level":3,"characters":{"K":{"response_time":1e300}}}"#;
+    ///     assert!(matches!(Trainer::from_json(json), Err(ProgressError::Format(_))));
+    /// }
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, ProgressError> {
+        let root: Value = Value::parse(json).map_err(ProgressError::Format)?;
+        let field = |name: &str| root.get(name).and_then(Value::as_f64);
+        let level: f64 =
+            field("level").ok_or_else(|| ProgressError::Format("niveau absent".to_string()))?;
+        let mut koch: Koch = Koch::new(level as usize);
+        if let Some(group_length) = field("group_length") {
+            koch.set_group_length(group_length as usize);
+        }
+        if let Some(group_count) = field("group_count") {
+            koch.set_group_count(group_count as usize);
+        }
+        if let Some(newest_weight) = field("newest_weight") {
+            koch.set_newest_weight(newest_weight as f32);
+        }
+        if let (Some(character_wpm), Some(effective_wpm)) =
+            (field("character_wpm"), field("effective_wpm"))
+        {
+            koch.set_speed(character_wpm as f32, effective_wpm as f32);
+        }
+        if let Some(seed) = root.get("seed").and_then(Value::as_str) {
+            koch.set_seed(
+                seed.parse()
+                    .map_err(|_| ProgressError::Format(format!("graine invalide : {}", seed)))?,
+            );
+        }
+        let mut trainer: Trainer = Trainer::new(koch);
+        if let Some(threshold) = field("threshold") {
+            trainer.set_threshold(threshold as f32);
+        }
+        trainer.sessions = field("sessions").unwrap_or(0.0) as usize;
+
+        let single = |text: &str| -> Result<char, ProgressError> {
+            let mut characters = text.chars();
+            match (characters.next(), characters.next()) {
+                (Some(character), None) => Ok(character),
+                _ => Err(ProgressError::Format(format!(
+                    "« {} » n'est pas un caractère",
+                    text
+                ))),
+            }
+        };
+        for (character, progress) in root
+            .get("characters")
+            .and_then(Value::as_object)
+            .unwrap_or_default()
+        {
+            let value = |name: &str| progress.get(name).and_then(Value::as_f64);
+            let response_time: Option<Duration> = value("response_time")
+                .map(|time| {
+                    Duration::try_from_secs_f64(time).map_err(|_| {
+                        ProgressError::Format(format!("temps de réponse invalide : {}", time))
+                    })
+                })
+                .transpose()?;
+            trainer.progress.insert(
+                single(character)?,
+                CharacterProgress {
+                    sent: value("sent").unwrap_or(0.0) as usize,
+                    errors: value("errors").unwrap_or(0.0) as usize,
+                    accuracy: value("accuracy").unwrap_or(0.0) as f32,
+                    response_time,
+                },
+            );
+        }
+        for confusion in root
+            .get("confusions")
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+        {
+            let text = |name: &str| confusion.get(name).and_then(Value::as_str).unwrap_or("");
+            let count: usize = confusion
+                .get("count")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0) as usize;
+            trainer
+                .confusions
+                .insert((single(text("sent"))?, single(text("copied"))?), count);
+        }
+        Ok(trainer)
+    }
+
+    /// Enregistre la progression dans un fichier JSON.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ProgressError> {
+        fs::write(path, self.to_json())?;
+        Ok(())
+    }
+
+    /// Charge une progression enregistrée par `save`.
+    ///
+    /// # Exemple
+    ///
+    /// ```rust
+    /// use crate::morseus::{Koch, Trainer};
+    ///
+    /// fn main() {
+    ///     let trainer = Trainer::new(Koch::new(12));
+    ///     trainer.save("eleve.json").unwrap();
+    ///     assert_eq!(Trainer::load("eleve.json").unwrap().level(), 12);
+    ///     std::fs::remove_file("eleve.json").unwrap();
+    /// }
+    /// ```
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ProgressError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}